@@ -8,6 +8,10 @@
     let c = opt.at(name, default: none)
     if c != none {
       if type(c) == color {
+        if c.space() == cmyk {
+          // passed as "cmyk(C,M,Y,K)" percentages so CMYK output stays exact
+          return "cmyk(" + c.components().map(it => str(int(calc.round(it / 1%)))).join(",") + ")"
+        }
         return c.to-hex().slice(1)
       } else if type(c) == str {
         if c.at(0) == "#" {
@@ -32,6 +36,11 @@
     result.insert("bg-color", bg-color)
   }
 
+  let rotation = result.at("rotation", default: none)
+  if type(rotation) == angle {
+    result.insert("rotation", int(calc.round(rotation.deg())))
+  }
+
   return result
 }
 
@@ -223,6 +223,10 @@ The following values are valid for the `options` dictionary:
   typst-type("float"),
   [Height in X-dimensions that EAN/UPC guard bars descend.],
   typst-val("5.0"),
+  [rotation],
+  typst-type("int,angle"),
+  [Clockwise rotation of the barcode in degrees; must be a multiple of 90, e.g. #typst-val("90") or #typst-val("-90deg") (same as #typst-val("270")).],
+  typst-val("0"),
 )
 
 #pagebreak()
@@ -385,6 +389,8 @@ Output options allow specifying how `Zint` should generate the barcode/symbol.
 As there's constants associated with `option_3` values, this package allows specifying the
 value as either an #typst-type("int") or a #typst-type("str").
 
+Values are checked against the selected symbology, so for instance #typst-val("\"square\"") is rejected for any symbology other than Data Matrix.
+
 The following table documents supported values and their #typst-type("str") representations:
 
 #tablex(
@@ -417,6 +423,43 @@ The following table documents supported values and their #typst-type("str") repr
   [Enable Ultracode compression *(experimental)*],
 )
 
+=== Symbology Options <symbology_options>
+
+Instead of raw `option-1` and `option-2` values, most symbologies accept readable fields which are checked against the selected symbology and override the raw values:
+
+#tablex(
+  columns: (auto, auto, 1fr),
+  align: (left + horizon, center + horizon, left + horizon),
+  ..reference-table-style(),
+  [*Field*],
+  [*Type*],
+  [*Symbologies and values*],
+  [ecc],
+  typst-type("int,str"),
+  [QR Code, Micro QR, rMQR: #typst-val("\"L\""), #typst-val("\"M\""), #typst-val("\"Q\""), #typst-val("\"H\""); Aztec: #typst-val("\"10%\""), #typst-val("\"23%\""), #typst-val("\"36%\""), #typst-val("\"50%\""); PDF417 (0-8), Han Xin (1-4), Grid Matrix (1-5), Ultracode (1-6): level],
+  [version],
+  typst-type("int"),
+  [Version or size of QR Code (1-40), Micro QR (1-4), rMQR (1-38), Data Matrix (1-48), Han Xin (1-84), Grid Matrix (1-13), Code One (1-10)],
+  [layers],
+  typst-type("int"),
+  [Aztec size (1-4 compact, 5-36 full-range)],
+  [columns],
+  typst-type("int"),
+  [PDF417 (1-30), MicroPDF417 (1-4), DotCode (5-200), Codablock-F (9-67)],
+  [rows],
+  typst-type("int"),
+  [Codablock-F (1-44)],
+  [check-digit],
+  typst-type("bool,str"),
+  [MSI Plessey: #typst-val("\"mod10\""), #typst-val("\"mod10-mod10\""), #typst-val("\"mod11\""), #typst-val("\"mod11-mod10\""), #typst-val("\"mod11-ncr\""), #typst-val("\"mod11-ncr-mod10\""); Code 11: #typst-val("\"none\""), #typst-val("\"one\""), #typst-val("\"two\""); Code 39, Code 2 of 5: #typst-val("\"visible\""), #typst-val("\"hidden\"") (Code 2 of 5 only)],
+  [addon-gap],
+  typst-type("int"),
+  [EAN/UPC add-on gap in X-dimensions (7-12; 9-12 for UPC-A)],
+  [mode],
+  typst-type("int"),
+  [MaxiCode mode (2-6)],
+)
+
 #pagebreak()
 = Examples <examples>
 
@@ -773,7 +816,7 @@ example-of-simple("UPC-E (chk)", "upce-chk", upce-chk, "12345670"),
 
 = Symbology Values <symbology>
 
-Following symbology values are supported:
+Following symbology values are supported. Matching ignores case and punctuation, and Zint names (e.g. #typst-val("\"BARCODE_QRCODE\"")), human readable names (e.g. #typst-val("\"QR Code\"")) and numeric IDs are accepted as well:
 #grid(
   columns: (1fr, 1fr, 1fr, 1fr, 1fr, 1fr),
   gutter: 5pt,
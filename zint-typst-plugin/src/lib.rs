@@ -19,8 +19,7 @@ type Result<T> = std::result::Result<T, crate::Error>;
 #[wasm_func]
 pub fn gen_with_options(options: &[u8], text: &[u8]) -> Result<Vec<u8>> {
     let options: Options = ciborium::from_reader(options)?;
-    let text = std::str::from_utf8(text).expect("non-utf8 string"); // bytes(data) always creates a utf8 slice
-    let symbol = Symbol::new(&options);
-    let svg = symbol.encode_svg(text, 0, 0)?;
+    let mut symbol = Symbol::new(&options);
+    let svg = symbol.encode_svg(text)?.into_output();
     Ok(svg.into_bytes())
 }
@@ -6,9 +6,17 @@ use zint_wasm_rs::{
 pub fn main() {
     let encoded_text = "6975004310001";
     let options = Options::with_symbology(Symbology::EANXChk);
-    let symbol = Symbol::new(&options);
-    match symbol.encode_svg(encoded_text, 0, 0) {
-        Ok(svg) => println!("{}", svg),
+    let mut symbol = Symbol::new(&options);
+    match symbol.encode_svg(encoded_text) {
+        Ok(svg) => {
+            if let Some(warning) = svg.warning {
+                eprintln!(
+                    "warning: {}",
+                    svg.message.unwrap_or_else(|| warning.to_string())
+                );
+            }
+            println!("{}", svg.output)
+        }
         Err(err) => println!("{:#?}", err),
     }
 }
@@ -0,0 +1,28 @@
+use zint_wasm_rs::{
+    options::{input_mode::InputMode, symbology::Symbology, symbology_options::QrEcc, Options},
+    symbol::{Segment, Symbol},
+};
+
+pub fn main() {
+    // UTF-8 input is converted by Zint into character set of each ECI
+    let segments = [
+        Segment::new("Žluťoučký kůň", 4), // ISO/IEC 8859-2
+        Segment::new("バーコード", 20),   // Shift JIS
+    ];
+    let options = Options::builder(Symbology::QRCode)
+        .input_mode(InputMode::UNICODE)
+        .ecc(QrEcc::M)
+        .build()
+        .expect("valid QR Code options");
+    let mut symbol = Symbol::new(&options);
+    match symbol.encode_svg(&segments) {
+        Ok(svg) => {
+            eprintln!(
+                "QR version {} ({}x{} modules)",
+                svg.info.option_2, svg.info.width, svg.info.rows
+            );
+            println!("{}", svg.output)
+        }
+        Err(err) => println!("{:#?}", err),
+    }
+}
@@ -0,0 +1,388 @@
+//! Check digit calculation and validation for linear symbologies.
+//!
+//! Each [`Scheme`] can compute check digits of data, append them to the data
+//! or verify data that already includes them, e.g. to decide whether to
+//! encode it with [`Symbology::EANX`] or [`Symbology::EANXChk`].
+//!
+//! ```
+//! # use zint_wasm_rs::check_digit::Scheme;
+//! assert_eq!(Scheme::Ean13.append("501234567890")?, "5012345678900");
+//! assert!(Scheme::Ean13.verify("5012345678901").is_err());
+//! # Ok::<(), zint_wasm_rs::error::Error>(())
+//! ```
+//!
+//! [`Symbology::EANX`]: crate::options::symbology::Symbology::EANX
+//! [`Symbology::EANXChk`]: crate::options::symbology::Symbology::EANXChk
+
+use std::fmt::Display;
+
+use crate::{error::Error, gs1::gs1_check_digit};
+
+/// Code 39 characters in order of their check value
+const CODE39_CHARSET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+/// Code 11 characters in order of their check value
+const CODE11_CHARSET: &str = "0123456789-";
+
+/// VIN position weights, including the check digit position
+const VIN_WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// Index of the check digit in VIN
+const VIN_CHECK_POSITION: usize = 8;
+
+/// Returns modulo 11 check character, using `X` for 10.
+fn mod11_char(value: u32) -> char {
+    match value {
+        10 => 'X',
+        _ => char::from_digit(value, 10).unwrap(),
+    }
+}
+
+/// Returns weighted sum of `values` from the right, with weights cycling
+/// from `min_weight` to `max_weight`.
+fn weighted_sum(values: &[u32], min_weight: u32, max_weight: u32) -> u32 {
+    let cycle = max_weight - min_weight + 1;
+    values
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, value)| value * (min_weight + i as u32 % cycle))
+        .sum()
+}
+
+/// Returns VIN transliteration value of `c`.
+fn vin_value(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => c.to_digit(10),
+        'A'..='H' => Some(c as u32 - 'A' as u32 + 1),
+        'J'..='N' => Some(c as u32 - 'J' as u32 + 1),
+        'P' => Some(7),
+        'R' => Some(9),
+        'S'..='Z' => Some(c as u32 - 'S' as u32 + 2),
+        _ => None,
+    }
+}
+
+/// Expands UPC-E number system and 6 digits into the equivalent UPC-A
+/// digits without check digit.
+fn expand_upce(data: &str) -> String {
+    let (ns, m) = data.split_at(1);
+    let m = m.as_bytes();
+    let s = |range: std::ops::Range<usize>| std::str::from_utf8(&m[range]).unwrap();
+    match m[5] {
+        b'0'..=b'2' => format!("{}{}{}0000{}", ns, s(0..2), s(5..6), s(2..5)),
+        b'3' => format!("{}{}00000{}", ns, s(0..3), s(3..5)),
+        b'4' => format!("{}{}00000{}", ns, s(0..4), s(4..5)),
+        _ => format!("{}{}0000{}", ns, s(0..5), s(5..6)),
+    }
+}
+
+/// Check digit scheme of a symbology or identification number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Scheme {
+    /// EAN-8, with 7 data digits
+    Ean8,
+    /// EAN-13, with 12 data digits
+    Ean13,
+    /// UPC-A, with 11 data digits
+    UpcA,
+    /// UPC-E, with number system (`0` or `1`) and 6 data digits
+    ///
+    /// The check digit is the one of the equivalent UPC-A number.
+    UpcE,
+    /// ISBN-10, with 9 data digits and `X` check digit for 10
+    ///
+    /// Lowercase `x` is accepted when verifying.
+    Isbn10,
+    /// ISBN-13, with 12 data digits starting with `978` or `979`
+    Isbn13,
+    /// ITF-14, with 13 data digits
+    Itf14,
+    /// EAN-14, with 13 data digits
+    Ean14,
+    /// SSCC or NVE, with 17 data digits
+    Sscc,
+    /// German Pharmazentralnummer (PZN-8), with 7 data digits
+    ///
+    /// PZN-7 numbers are the same with a leading `0`.
+    Pzn,
+    /// Vehicle Identification Number, with the check digit in 9th position
+    ///
+    /// Like with ISBN-10, lowercase `x` check digit is accepted when
+    /// verifying.
+    Vin,
+    /// Code 39 modulo 43 check character
+    Code39Mod43,
+    /// MSI Plessey modulo 10 (Luhn) check digit
+    MsiMod10,
+    /// MSI Plessey modulo 11 (IBM weights) check digit
+    ///
+    /// Check value 10 is written as two digits `10`, like Zint does.
+    MsiMod11,
+    /// Code 11 `C` and `K` check digits
+    Code11,
+}
+
+impl Scheme {
+    pub const ALL: &'static [Scheme] = &[
+        Scheme::Ean8,
+        Scheme::Ean13,
+        Scheme::UpcA,
+        Scheme::UpcE,
+        Scheme::Isbn10,
+        Scheme::Isbn13,
+        Scheme::Itf14,
+        Scheme::Ean14,
+        Scheme::Sscc,
+        Scheme::Pzn,
+        Scheme::Vin,
+        Scheme::Code39Mod43,
+        Scheme::MsiMod10,
+        Scheme::MsiMod11,
+        Scheme::Code11,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scheme::Ean8 => "EAN-8",
+            Scheme::Ean13 => "EAN-13",
+            Scheme::UpcA => "UPC-A",
+            Scheme::UpcE => "UPC-E",
+            Scheme::Isbn10 => "ISBN-10",
+            Scheme::Isbn13 => "ISBN-13",
+            Scheme::Itf14 => "ITF-14",
+            Scheme::Ean14 => "EAN-14",
+            Scheme::Sscc => "SSCC",
+            Scheme::Pzn => "PZN",
+            Scheme::Vin => "VIN",
+            Scheme::Code39Mod43 => "Code 39 mod 43",
+            Scheme::MsiMod10 => "MSI mod 10",
+            Scheme::MsiMod11 => "MSI mod 11",
+            Scheme::Code11 => "Code 11",
+        }
+    }
+
+    /// Returns number of data characters (without check digits) for fixed
+    /// length schemes.
+    pub fn data_len(&self) -> Option<usize> {
+        match self {
+            Scheme::Ean8 | Scheme::UpcE | Scheme::Pzn => Some(7),
+            Scheme::Ean13 | Scheme::Isbn13 => Some(12),
+            Scheme::UpcA => Some(11),
+            Scheme::Isbn10 => Some(9),
+            Scheme::Itf14 | Scheme::Ean14 => Some(13),
+            Scheme::Sscc => Some(17),
+            Scheme::Vin => Some(16),
+            Scheme::Code39Mod43 | Scheme::MsiMod10 | Scheme::MsiMod11 | Scheme::Code11 => None,
+        }
+    }
+
+    fn invalid(&self, reason: String) -> Error {
+        Error::InvalidCheckDigitData {
+            scheme: *self,
+            reason,
+        }
+    }
+
+    /// Returns check digits of `data`, which doesn't include them.
+    pub fn compute(&self, data: &str) -> Result<String, Error> {
+        let len = data.chars().count();
+        match self.data_len() {
+            Some(expected) if len != expected => {
+                return Err(self.invalid(format!(
+                    "must have {} characters without check digit; got {}",
+                    expected, len
+                )))
+            }
+            None if len == 0 => return Err(self.invalid("data is empty".to_string())),
+            _ => {}
+        }
+        let values = |charset: &str| -> Result<Vec<u32>, Error> {
+            data.chars()
+                .map(|c| {
+                    charset
+                        .find(c)
+                        .map(|it| it as u32)
+                        .ok_or_else(|| self.invalid(format!("character {:?} isn't allowed", c)))
+                })
+                .collect()
+        };
+        let digits = || values("0123456789");
+
+        let check = match self {
+            Scheme::Ean8
+            | Scheme::Ean13
+            | Scheme::UpcA
+            | Scheme::Itf14
+            | Scheme::Ean14
+            | Scheme::Sscc => {
+                digits()?;
+                gs1_check_digit(data).to_string()
+            }
+            Scheme::Isbn13 => {
+                digits()?;
+                if !data.starts_with("978") && !data.starts_with("979") {
+                    return Err(self.invalid("must start with 978 or 979".to_string()));
+                }
+                gs1_check_digit(data).to_string()
+            }
+            Scheme::UpcE => {
+                digits()?;
+                if !data.starts_with(['0', '1']) {
+                    return Err(self.invalid("number system must be 0 or 1".to_string()));
+                }
+                gs1_check_digit(&expand_upce(data)).to_string()
+            }
+            Scheme::Isbn10 => {
+                let sum: u32 = digits()?
+                    .iter()
+                    .enumerate()
+                    .map(|(i, digit)| (i as u32 + 1) * digit)
+                    .sum();
+                mod11_char(sum % 11).to_string()
+            }
+            Scheme::Pzn => {
+                let sum: u32 = digits()?
+                    .iter()
+                    .enumerate()
+                    .map(|(i, digit)| (i as u32 + 1) * digit)
+                    .sum();
+                if sum % 11 == 10 {
+                    return Err(self.invalid("number has no valid check digit".to_string()));
+                }
+                (sum % 11).to_string()
+            }
+            Scheme::Vin => {
+                let weights = VIN_WEIGHTS[..VIN_CHECK_POSITION]
+                    .iter()
+                    .chain(&VIN_WEIGHTS[VIN_CHECK_POSITION + 1..]);
+                let mut sum = 0;
+                for (c, weight) in data.chars().zip(weights) {
+                    let value = vin_value(c)
+                        .ok_or_else(|| self.invalid(format!("character {:?} isn't allowed", c)))?;
+                    sum += value * weight;
+                }
+                mod11_char(sum % 11).to_string()
+            }
+            Scheme::Code39Mod43 => {
+                let sum: u32 = values(CODE39_CHARSET)?.iter().sum();
+                CODE39_CHARSET[(sum % 43) as usize..][..1].to_string()
+            }
+            Scheme::MsiMod10 => {
+                let sum: u32 = digits()?
+                    .iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(i, digit)| match i % 2 {
+                        0 if *digit >= 5 => digit * 2 - 9,
+                        0 => digit * 2,
+                        _ => *digit,
+                    })
+                    .sum();
+                ((10 - sum % 10) % 10).to_string()
+            }
+            Scheme::MsiMod11 => {
+                let sum = weighted_sum(&digits()?, 2, 7);
+                ((11 - sum % 11) % 11).to_string()
+            }
+            Scheme::Code11 => {
+                let mut values = values(CODE11_CHARSET)?;
+                let c = weighted_sum(&values, 1, 10) % 11;
+                values.push(c);
+                let k = weighted_sum(&values, 1, 9) % 11;
+                [c, k]
+                    .iter()
+                    .map(|it| CODE11_CHARSET.as_bytes()[*it as usize] as char)
+                    .collect()
+            }
+        };
+        Ok(check)
+    }
+
+    /// Returns `data` with its check digits added.
+    pub fn append(&self, data: &str) -> Result<String, Error> {
+        let check = self.compute(data)?;
+        Ok(match self {
+            Scheme::Vin => {
+                let (head, tail) = data.split_at(VIN_CHECK_POSITION);
+                format!("{}{}{}", head, check, tail)
+            }
+            _ => format!("{}{}", data, check),
+        })
+    }
+
+    /// Verifies check digits of `data`, which includes them.
+    ///
+    /// Returns [`Error::CheckDigitMismatch`] with the expected check digits
+    /// if they don't match.
+    pub fn verify(&self, data: &str) -> Result<(), Error> {
+        let check_len = match self {
+            Scheme::Code11 => 2,
+            Scheme::MsiMod11 if data.ends_with("10") => {
+                if let Ok(check) = self.compute(&data[..data.len() - 2]) {
+                    if check == "10" {
+                        return Ok(());
+                    }
+                }
+                1
+            }
+            _ => 1,
+        };
+        let len = data.chars().count();
+        if !data.is_ascii() || len <= check_len {
+            return Err(self.invalid(format!(
+                "must be ASCII and include check digit; got {:?}",
+                data
+            )));
+        }
+        if let Some(expected) = self.data_len() {
+            if len != expected + check_len {
+                return Err(self.invalid(format!(
+                    "must have {} characters with check digit; got {}",
+                    expected + check_len,
+                    len
+                )));
+            }
+        }
+
+        let (without, actual) = match self {
+            Scheme::Vin => (
+                format!(
+                    "{}{}",
+                    &data[..VIN_CHECK_POSITION],
+                    &data[VIN_CHECK_POSITION + 1..]
+                ),
+                &data[VIN_CHECK_POSITION..VIN_CHECK_POSITION + 1],
+            ),
+            _ => {
+                let (without, actual) = data.split_at(len - check_len);
+                (without.to_string(), actual)
+            }
+        };
+        let expected = self.compute(&without)?;
+        let matches = match self {
+            Scheme::Isbn10 | Scheme::Vin => expected.eq_ignore_ascii_case(actual),
+            _ => expected == actual,
+        };
+        if !matches {
+            return Err(Error::CheckDigitMismatch {
+                scheme: *self,
+                expected,
+                actual: actual.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns whether `data` includes valid check digits.
+    pub fn is_valid(&self, data: &str) -> bool {
+        self.verify(data).is_ok()
+    }
+}
+
+impl Display for Scheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
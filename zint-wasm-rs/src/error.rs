@@ -1,8 +1,13 @@
-use std::{fmt::Display, mem::MaybeUninit, str::Utf8Error};
+use std::{fmt::Display, mem::MaybeUninit, ops::RangeInclusive, str::Utf8Error};
 
 use serde::Deserialize;
 use zint_wasm_sys::*;
 
+use crate::{
+    check_digit::Scheme,
+    options::{capability::CapabilityFlags, symbology::Symbology, symbology_options::QrEcc},
+};
+
 macro_rules! in_range_or_other {
     ($owner: ident, $repr: ty) => {
         impl From<$repr> for $owner {
@@ -210,9 +215,40 @@ impl Display for ValidationFailiure {
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    /// Error originating from Zint
-    #[error(transparent)]
-    Zint(#[from] ZintError),
+    /// Error originating from Zint, along with its `errtxt` message
+    #[error("{}", zint_message(.kind, .message))]
+    Zint {
+        kind: ZintError,
+        message: Option<String>,
+    },
+    /// Data is too long to be passed to Zint
+    #[error("data has {size} bytes which exceeds maximum length supported by zint")]
+    DataTooLong { size: usize },
+    /// Requested feature isn't supported by the symbology
+    #[error("symbology doesn't support {0:?}")]
+    UnsupportedCapability(CapabilityFlags),
+    /// Option can't be used with the symbology
+    #[error("{which} option isn't supported by {symbology}")]
+    IncompatibleOption {
+        which: &'static str,
+        symbology: Symbology,
+    },
+    /// Error correction level isn't supported by the symbology (e.g. Micro
+    /// QR lacks H and rMQR only supports M and H)
+    #[error("ecc level {ecc:?} isn't supported by {symbology}")]
+    UnsupportedEcc { ecc: QrEcc, symbology: Symbology },
+    /// Option value is out of range for the symbology
+    #[error(
+        "{which} must be between {} and {} for {symbology}; got {value}",
+        .range.start(),
+        .range.end()
+    )]
+    OutOfRange {
+        which: &'static str,
+        value: f32,
+        range: RangeInclusive<f32>,
+        symbology: Symbology,
+    },
     #[error("zint returned non-utf8 SVG result")]
     InvalidResultSVG(#[source] Utf8Error),
     /// Invalid output options
@@ -236,6 +272,34 @@ pub enum Error {
     /// Invalid color format
     #[error("invalid color format; {reason}")]
     InvalidColor { reason: &'static str },
+    /// Invalid Structured Append configuration
+    #[error("invalid structured append; {reason}")]
+    InvalidStructuredAppend { reason: &'static str },
+    /// Invalid GS1 element string or data
+    #[error("invalid GS1 data; {reason}")]
+    InvalidGs1 { reason: String },
+    /// Invalid or unsupported GS1 Digital Link URI
+    #[error("invalid GS1 Digital Link; {reason}")]
+    InvalidDigitalLink { reason: &'static str },
+    /// Invalid MaxiCode carrier message
+    #[error("invalid MaxiCode message; {reason}")]
+    InvalidMaxiCodeMessage { reason: String },
+    /// Invalid USPS Intelligent Mail barcode fields
+    #[error("invalid Intelligent Mail barcode; {reason}")]
+    InvalidIntelligentMail { reason: String },
+    /// Data can't be used with the check digit scheme
+    #[error("invalid {scheme} data; {reason}")]
+    InvalidCheckDigitData { scheme: Scheme, reason: String },
+    /// Check digits don't match data
+    #[error("{scheme} check digit should be {expected}; got {actual}")]
+    CheckDigitMismatch {
+        scheme: Scheme,
+        expected: String,
+        actual: String,
+    },
+    /// Invalid or unsupported `zint` command line argument
+    #[error("invalid command line argument `{arg}`; {reason}")]
+    InvalidCliArgument { arg: String, reason: &'static str },
     #[error("invalid option value for {which}: {value:?}")]
     UnknownOption {
         which: &'static str,
@@ -243,6 +307,22 @@ pub enum Error {
     },
 }
 
+impl From<ZintError> for Error {
+    fn from(kind: ZintError) -> Self {
+        Error::Zint {
+            kind,
+            message: None,
+        }
+    }
+}
+
+fn zint_message(kind: &ZintError, message: &Option<String>) -> String {
+    match message {
+        Some(message) => message.clone(),
+        None => kind.to_string(),
+    }
+}
+
 /// Warning level (symbol->warn_level)
 #[derive(Debug, Copy, Clone, Deserialize)]
 #[repr(u32)]
@@ -0,0 +1,717 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    error::Error,
+    options::{input_mode::InputMode, symbology::Symbology, Options},
+};
+
+use super::{Element, ElementString};
+
+/// Primary key AIs along with their key qualifiers, in the order they appear
+/// in Digital Link URI path
+const PRIMARY_KEYS: &[(&str, &[&str])] = &[
+    ("01", &["22", "10", "21"]),
+    ("8006", &["22", "10", "21"]),
+    ("8013", &["7040"]),
+    ("00", &[]),
+    ("414", &["254", "7040"]),
+    ("417", &["7040"]),
+    ("8004", &["7040"]),
+    ("8003", &[]),
+    ("8010", &["8011"]),
+    ("8017", &["8019"]),
+    ("8018", &["8019"]),
+    ("253", &[]),
+    ("255", &[]),
+    ("401", &[]),
+    ("402", &[]),
+];
+
+/// Legacy short names accepted in place of AIs in URI path
+const SHORT_NAMES: &[(&str, &str)] = &[
+    ("sscc", "00"),
+    ("gtin", "01"),
+    ("lot", "10"),
+    ("ser", "21"),
+    ("cpv", "22"),
+    ("gln", "414"),
+];
+
+fn invalid(reason: &'static str) -> Error {
+    Error::InvalidDigitalLink { reason }
+}
+
+fn qualifiers(primary_key: &str) -> Option<&'static [&'static str]> {
+    PRIMARY_KEYS
+        .iter()
+        .find(|(key, _)| *key == primary_key)
+        .map(|(_, qualifiers)| *qualifiers)
+}
+
+/// Returns AI for a path segment, resolving short names.
+fn path_ai(segment: &str) -> &str {
+    SHORT_NAMES
+        .iter()
+        .find(|(name, _)| *name == segment)
+        .map(|(_, ai)| *ai)
+        .unwrap_or(segment)
+}
+
+fn is_ai(s: &str) -> bool {
+    (2..=4).contains(&s.len()) && s.bytes().all(|it| it.is_ascii_digit())
+}
+
+/// Returns whether a path segment looks like compressed Digital Link data,
+/// which is base64url encoded.
+fn is_compressed(segment: &str) -> bool {
+    !segment.is_empty() && segment.bytes().all(|it| BASE64URL.contains(&it))
+}
+
+/// Alphabet of base64url encoding used by compressed URIs
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Component of an AI data field
+#[derive(Debug, Clone, Copy)]
+enum Component {
+    /// Fixed length numeric
+    Numeric(usize),
+    /// Numeric of up to given length
+    VarNumeric(usize),
+    /// Alphanumeric of up to given length
+    Alphanumeric(usize),
+}
+
+use Component::{Alphanumeric, Numeric, VarNumeric};
+
+/// Data field formats of AIs that can be written in compressed URIs, from
+/// GS1 General Specifications
+const FORMATS: &[(&str, &[Component])] = &[
+    ("00", &[Numeric(18)]),
+    ("01", &[Numeric(14)]),
+    ("02", &[Numeric(14)]),
+    ("10", &[Alphanumeric(20)]),
+    ("11", &[Numeric(6)]),
+    ("13", &[Numeric(6)]),
+    ("15", &[Numeric(6)]),
+    ("16", &[Numeric(6)]),
+    ("17", &[Numeric(6)]),
+    ("20", &[Numeric(2)]),
+    ("21", &[Alphanumeric(20)]),
+    ("22", &[Alphanumeric(20)]),
+    ("253", &[Numeric(13), Alphanumeric(17)]),
+    ("254", &[Alphanumeric(20)]),
+    ("255", &[Numeric(13), VarNumeric(12)]),
+    ("30", &[VarNumeric(8)]),
+    ("37", &[VarNumeric(8)]),
+    ("400", &[Alphanumeric(30)]),
+    ("401", &[Alphanumeric(30)]),
+    ("402", &[Numeric(17)]),
+    ("410", &[Numeric(13)]),
+    ("414", &[Numeric(13)]),
+    ("417", &[Numeric(13)]),
+    ("422", &[Numeric(3)]),
+    ("8003", &[Numeric(14), Alphanumeric(16)]),
+    ("8004", &[Alphanumeric(30)]),
+    ("8006", &[Numeric(14), Numeric(2), Numeric(2)]),
+    ("8010", &[Alphanumeric(30)]),
+    ("8011", &[VarNumeric(12)]),
+    ("8013", &[Alphanumeric(25)]),
+    ("8017", &[Numeric(18)]),
+    ("8018", &[Numeric(18)]),
+    ("8019", &[VarNumeric(10)]),
+];
+
+/// Returns length of AIs starting with two digit `prefix`.
+fn ai_length(prefix: &str) -> Option<usize> {
+    Some(match prefix.parse::<u8>().ok()? {
+        0..=4 | 10..=22 | 30 | 37 | 90..=99 => 2,
+        23..=25 | 40..=42 | 71 => 3,
+        31..=36 | 39 | 43 | 70 | 72 | 80..=82 => 4,
+        _ => return None,
+    })
+}
+
+fn format(ai: &str) -> Option<&'static [Component]> {
+    let measurement = ai.len() == 4
+        && ["310", "311", "320", "330"].contains(&&ai[..3])
+        && ai.as_bytes()[3] <= b'5';
+    if measurement {
+        return Some(&[Numeric(6)]);
+    }
+    FORMATS
+        .iter()
+        .find(|(it, _)| *it == ai)
+        .map(|(_, format)| *format)
+}
+
+/// Number of bits needed for a length of up to `max_len`
+fn length_bits(max_len: usize) -> usize {
+    (usize::BITS - max_len.leading_zeros()) as usize
+}
+
+/// Number of bits needed for a numeric value of `len` digits
+fn value_bits(len: usize) -> usize {
+    (0..)
+        .find(|bits| 2f64.powi(*bits) >= 10f64.powi(len as i32))
+        .unwrap() as usize
+}
+
+/// Encoding of alphanumeric components, selected by the 3 bit indicator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Numeric = 0,
+    LowerHex = 1,
+    UpperHex = 2,
+    Base64Url = 3,
+    Ascii = 4,
+}
+
+impl Encoding {
+    /// Returns the shortest encoding that can represent `value`.
+    fn of(value: &str) -> Self {
+        let bytes = value.as_bytes();
+        if bytes.iter().all(u8::is_ascii_digit) {
+            Encoding::Numeric
+        } else if bytes
+            .iter()
+            .all(|it| matches!(it, b'0'..=b'9' | b'a'..=b'f'))
+        {
+            Encoding::LowerHex
+        } else if bytes
+            .iter()
+            .all(|it| matches!(it, b'0'..=b'9' | b'A'..=b'F'))
+        {
+            Encoding::UpperHex
+        } else if bytes.iter().all(|it| BASE64URL.contains(it)) {
+            Encoding::Base64Url
+        } else {
+            Encoding::Ascii
+        }
+    }
+
+    /// Returns characters indexed by their encoded value, or `None` if
+    /// characters are encoded as themselves.
+    fn alphabet(self) -> Option<&'static [u8]> {
+        match self {
+            Encoding::LowerHex => Some(b"0123456789abcdef"),
+            Encoding::UpperHex => Some(b"0123456789ABCDEF"),
+            Encoding::Base64Url => Some(BASE64URL),
+            Encoding::Numeric | Encoding::Ascii => None,
+        }
+    }
+
+    /// Number of bits of each character; numeric values are encoded as a
+    /// whole instead.
+    fn char_bits(self) -> usize {
+        match self {
+            Encoding::LowerHex | Encoding::UpperHex => 4,
+            Encoding::Base64Url => 6,
+            Encoding::Numeric | Encoding::Ascii => 7,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
+
+impl BitWriter {
+    fn push(&mut self, value: u128, bits: usize) {
+        self.bits
+            .extend((0..bits).rev().map(|i| (value >> i) & 1 == 1));
+    }
+
+    fn push_numeric(&mut self, digits: &str) {
+        // values of up to 30 digits fit into 100 bits
+        self.push(digits.parse().unwrap_or_default(), value_bits(digits.len()));
+    }
+
+    /// Returns bits as base64url, padded with zeros to whole characters.
+    fn to_base64url(&self) -> String {
+        self.bits
+            .chunks(6)
+            .map(|chunk| {
+                let index = (0..6).fold(0, |acc, i| {
+                    acc << 1 | chunk.get(i).copied().unwrap_or_default() as usize
+                });
+                BASE64URL[index] as char
+            })
+            .collect()
+    }
+}
+
+struct BitReader {
+    bits: Vec<bool>,
+    position: usize,
+}
+
+impl BitReader {
+    fn from_base64url(data: &str) -> Self {
+        let mut writer = BitWriter::default();
+        for byte in data.bytes() {
+            let index = BASE64URL
+                .iter()
+                .position(|it| *it == byte)
+                .unwrap_or_default();
+            writer.push(index as u128, 6);
+        }
+        BitReader {
+            bits: writer.bits,
+            position: 0,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.bits.len() - self.position
+    }
+
+    fn read(&mut self, bits: usize) -> Result<u128, Error> {
+        if bits > self.remaining() {
+            return Err(invalid("compressed data is truncated"));
+        }
+        let value = self.bits[self.position..self.position + bits]
+            .iter()
+            .fold(0, |acc, bit| acc << 1 | *bit as u128);
+        self.position += bits;
+        Ok(value)
+    }
+
+    fn read_numeric(&mut self, len: usize) -> Result<String, Error> {
+        let value = self.read(value_bits(len))?;
+        if value >= 10u128.pow(len as u32) {
+            return Err(invalid("compressed numeric value is too large"));
+        }
+        Ok(format!("{:0width$}", value, width = len))
+    }
+}
+
+/// Appends compressed `element` to `writer`.
+fn compress(writer: &mut BitWriter, element: &Element) -> Result<(), Error> {
+    let ai = element.ai();
+    let value = element.value();
+    let format = format(&ai).ok_or(invalid("AI isn't supported in compressed URIs"))?;
+    for digit in ai.bytes() {
+        writer.push((digit - b'0') as u128, 4);
+    }
+
+    let mut rest = value.as_str();
+    let is_numeric = |value: &str| value.bytes().all(|it| it.is_ascii_digit());
+    for component in format {
+        match *component {
+            Numeric(len) => {
+                let digits = rest.get(..len).filter(|it| is_numeric(it));
+                let digits = digits.ok_or(invalid("invalid numeric value in compressed URI"))?;
+                writer.push_numeric(digits);
+                rest = &rest[len..];
+            }
+            VarNumeric(max_len) => {
+                if rest.len() > max_len || !is_numeric(rest) {
+                    return Err(invalid("invalid numeric value in compressed URI"));
+                }
+                writer.push(rest.len() as u128, length_bits(max_len));
+                writer.push_numeric(rest);
+                rest = "";
+            }
+            Alphanumeric(max_len) => {
+                if rest.len() > max_len || !rest.is_ascii() {
+                    return Err(invalid("invalid alphanumeric value in compressed URI"));
+                }
+                let encoding = Encoding::of(rest);
+                writer.push(encoding as u128, 3);
+                writer.push(rest.len() as u128, length_bits(max_len));
+                if encoding == Encoding::Numeric {
+                    writer.push_numeric(rest);
+                } else {
+                    for byte in rest.bytes() {
+                        let index = encoding.alphabet().map_or(byte as usize, |alphabet| {
+                            alphabet.iter().position(|it| *it == byte).unwrap()
+                        });
+                        writer.push(index as u128, encoding.char_bits());
+                    }
+                }
+                rest = "";
+            }
+        }
+    }
+    if !rest.is_empty() {
+        return Err(invalid("invalid numeric value in compressed URI"));
+    }
+    Ok(())
+}
+
+/// Reads the next compressed element from `reader`.
+fn decompress(reader: &mut BitReader) -> Result<Element, Error> {
+    let read_digit = |reader: &mut BitReader| match reader.read(4)? {
+        digit @ 0..=9 => Ok((b'0' + digit as u8) as char),
+        _ => Err(invalid("optimized compressed URIs aren't supported")),
+    };
+    let mut ai = String::new();
+    for _ in 0..2 {
+        ai.push(read_digit(reader)?);
+    }
+    let len = ai_length(&ai).ok_or(invalid("unknown AI in compressed URI"))?;
+    for _ in 2..len {
+        ai.push(read_digit(reader)?);
+    }
+    let format = format(&ai).ok_or(invalid("AI isn't supported in compressed URIs"))?;
+
+    let mut value = String::new();
+    for component in format {
+        match *component {
+            Numeric(len) => value.push_str(&reader.read_numeric(len)?),
+            VarNumeric(max_len) => {
+                let len = reader.read(length_bits(max_len))? as usize;
+                if len > max_len {
+                    return Err(invalid("compressed value is too long"));
+                }
+                value.push_str(&reader.read_numeric(len)?);
+            }
+            Alphanumeric(max_len) => {
+                let encoding = match reader.read(3)? {
+                    0 => Encoding::Numeric,
+                    1 => Encoding::LowerHex,
+                    2 => Encoding::UpperHex,
+                    3 => Encoding::Base64Url,
+                    4 => Encoding::Ascii,
+                    _ => return Err(invalid("unknown encoding in compressed URI")),
+                };
+                let len = reader.read(length_bits(max_len))? as usize;
+                if len > max_len {
+                    return Err(invalid("compressed value is too long"));
+                }
+                if encoding == Encoding::Numeric {
+                    value.push_str(&reader.read_numeric(len)?);
+                    continue;
+                }
+                for _ in 0..len {
+                    let index = reader.read(encoding.char_bits())? as usize;
+                    let byte = encoding
+                        .alphabet()
+                        .map_or(index as u8, |alphabet| alphabet[index]);
+                    value.push(byte as char);
+                }
+            }
+        }
+    }
+    Element::parse(&ai, &value)
+}
+
+/// Percent-encodes everything except unreserved URI characters.
+fn percent_encode(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                result.push(byte as char)
+            }
+            _ => result.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    result
+}
+
+fn percent_decode(value: &str) -> Result<String, Error> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let hex = [
+                input.next().ok_or(invalid("incomplete percent-encoding"))?,
+                input.next().ok_or(invalid("incomplete percent-encoding"))?,
+            ];
+            let decoded = std::str::from_utf8(&hex)
+                .ok()
+                .and_then(|it| u8::from_str_radix(it, 16).ok())
+                .ok_or(invalid("invalid percent-encoding"))?;
+            bytes.push(decoded);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid("percent-encoded data isn't UTF-8"))
+}
+
+/// GS1 Digital Link URI, e.g.
+/// `https://id.gs1.org/01/09501101530003/10/ABC?17=250101`.
+///
+/// The primary key (e.g. GTIN) and its qualifiers are written in the URI
+/// path, other elements as query parameters:
+///
+/// ```
+/// # use zint_wasm_rs::gs1::{digital_link::DigitalLink, Date, Element, ElementString};
+/// let elements = ElementString::new()
+///     .with(Element::Batch("ABC".to_string()))?
+///     .with(Element::Expiry(Date::new(2025, 1, 1)?))?
+///     .with(Element::gtin("9501101530003")?)?;
+/// let link = DigitalLink::new(elements)?;
+/// let uri = "https://id.gs1.org/01/09501101530003/10/ABC?17=250101";
+/// assert_eq!(link.to_string(), uri);
+/// assert_eq!(uri.parse::<DigitalLink>()?.to_string(), uri);
+/// # Ok::<(), zint_wasm_rs::error::Error>(())
+/// ```
+///
+/// The shorter compressed form, which encodes all elements as binary data in
+/// a single path segment, is written by
+/// [`to_compressed_string`](DigitalLink::to_compressed_string) and accepted
+/// when parsed:
+///
+/// ```
+/// # use zint_wasm_rs::gs1::{digital_link::DigitalLink, Element, ElementString};
+/// let elements = ElementString::new().with(Element::gtin("5412345000013")?)?;
+/// let link = DigitalLink::new(elements)?;
+/// let uri = link.to_compressed_string()?;
+/// assert_eq!(uri, "https://id.gs1.org/AQnYUc1gmg");
+/// assert_eq!(uri.parse::<DigitalLink>()?, link);
+/// # Ok::<(), zint_wasm_rs::error::Error>(())
+/// ```
+///
+/// Digital Link URIs are encoded as plain data with
+/// [`Symbology::QRCode`] or [`Symbology::DataMatrix`]:
+///
+/// ```no_run
+/// # use zint_wasm_rs::{gs1::{digital_link::DigitalLink, Element, ElementString}, options::symbology::Symbology, symbol::Symbol};
+/// let elements = ElementString::new().with(Element::gtin("9501101530003")?)?;
+/// let link = DigitalLink::new(elements)?;
+/// let mut symbol = Symbol::new(&link.options(Symbology::QRCode)?);
+/// let svg = symbol.encode_svg(&link.to_string())?;
+/// # Ok::<(), zint_wasm_rs::error::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitalLink {
+    domain: String,
+    elements: ElementString,
+}
+
+impl DigitalLink {
+    /// Domain of the GS1 resolver
+    pub const DEFAULT_DOMAIN: &'static str = "https://id.gs1.org";
+
+    /// Creates a Digital Link of `elements` using the GS1 resolver domain.
+    ///
+    /// Returns an error if `elements` don't contain a primary key.
+    pub fn new(elements: ElementString) -> Result<Self, Error> {
+        let result = DigitalLink {
+            domain: Self::DEFAULT_DOMAIN.to_string(),
+            elements,
+        };
+        result.primary_key().ok_or(invalid("missing primary key"))?;
+        Ok(result)
+    }
+
+    /// Sets `http` or `https` domain (optionally with a path prefix) used
+    /// instead of the GS1 resolver.
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Result<Self, Error> {
+        let domain = domain.into();
+        let domain = domain.trim_end_matches('/');
+        let Some(host) = domain
+            .strip_prefix("https://")
+            .or_else(|| domain.strip_prefix("http://"))
+        else {
+            return Err(invalid("domain must start with http:// or https://"));
+        };
+        if host.is_empty() || host.contains(['?', '#']) {
+            return Err(invalid("domain must be a host with an optional path"));
+        }
+        self.domain = domain.to_string();
+        Ok(self)
+    }
+
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    pub fn elements(&self) -> &ElementString {
+        &self.elements
+    }
+
+    pub fn into_elements(self) -> ElementString {
+        self.elements
+    }
+
+    /// Returns the primary key element, which is the first element with a
+    /// primary key AI.
+    pub fn primary_key(&self) -> Option<&Element> {
+        self.elements
+            .elements()
+            .iter()
+            .find(|it| qualifiers(&it.ai()).is_some())
+    }
+
+    /// Returns the compressed form of the URI, e.g.
+    /// `https://id.gs1.org/AQnYUc1gmg`.
+    ///
+    /// Elements are written in the same order as in the uncompressed URI,
+    /// without the optional optimizations of common AI combinations.
+    /// Compression needs the data field format of every AI, so it's only
+    /// supported for typed [`Element`]s and the primary keys and key
+    /// qualifiers of Digital Link URIs; other AIs return
+    /// [`Error::InvalidDigitalLink`].
+    pub fn to_compressed_string(&self) -> Result<String, Error> {
+        let (in_path, query) = self.split_path();
+        let mut writer = BitWriter::default();
+        for element in in_path.into_iter().chain(query) {
+            compress(&mut writer, element)?;
+        }
+        Ok(format!("{}/{}", self.domain, writer.to_base64url()))
+    }
+
+    /// Splits elements into the primary key with its qualifiers, in path
+    /// order, and the other elements.
+    fn split_path(&self) -> (Vec<&Element>, Vec<&Element>) {
+        let mut in_path = Vec::new();
+        if let Some(primary_key) = self.primary_key() {
+            in_path.push(primary_key);
+            for qualifier in qualifiers(&primary_key.ai()).unwrap_or_default() {
+                in_path.extend(self.elements.get(qualifier));
+            }
+        }
+        let query = self
+            .elements
+            .elements()
+            .iter()
+            .filter(|it| !in_path.contains(it))
+            .collect();
+        (in_path, query)
+    }
+
+    /// Returns options for encoding the URI with `symbology`.
+    ///
+    /// Only QR Code and Data Matrix are supported.
+    pub fn options(&self, symbology: Symbology) -> Result<Options, Error> {
+        if !matches!(symbology, Symbology::QRCode | Symbology::DataMatrix) {
+            return Err(Error::IncompatibleOption {
+                which: "Digital Link",
+                symbology,
+            });
+        }
+        Options::builder(symbology)
+            .input_mode(InputMode::UNICODE)
+            .build()
+    }
+}
+
+impl TryFrom<ElementString> for DigitalLink {
+    type Error = Error;
+
+    fn try_from(elements: ElementString) -> Result<Self, Self::Error> {
+        DigitalLink::new(elements)
+    }
+}
+
+impl From<DigitalLink> for ElementString {
+    fn from(link: DigitalLink) -> Self {
+        link.elements
+    }
+}
+
+impl Display for DigitalLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.domain)?;
+        let (in_path, query) = self.split_path();
+        for element in in_path {
+            write!(f, "/{}/{}", element.ai(), percent_encode(&element.value()))?;
+        }
+        for (i, element) in query.into_iter().enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(
+                f,
+                "{}{}={}",
+                separator,
+                element.ai(),
+                percent_encode(&element.value())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DigitalLink {
+    type Err = Error;
+
+    /// Parses an uncompressed or compressed Digital Link URI.
+    ///
+    /// Legacy short names (e.g. `gtin` or `lot`) are accepted in the path,
+    /// GTIN-8, GTIN-12 and GTIN-13 primary keys are padded to 14 digits and
+    /// query parameters other than AIs are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.split_once('#').map_or(s, |(uri, _)| uri);
+        let (path, query) = s.split_once('?').unwrap_or((s, ""));
+        let Some(rest) = path
+            .strip_prefix("https://")
+            .or_else(|| path.strip_prefix("http://"))
+        else {
+            return Err(invalid("URI must start with http:// or https://"));
+        };
+        let segments: Vec<&str> = rest.split('/').collect();
+
+        // primary key is the first AI followed by a value and its qualifiers
+        let start = (1..segments.len()).find(|i| {
+            let Some(qualifiers) = qualifiers(path_ai(segments[*i])) else {
+                return false;
+            };
+            let pairs = &segments[*i..];
+            if !pairs.len().is_multiple_of(2) {
+                return false;
+            }
+            let mut allowed = qualifiers.iter();
+            pairs
+                .chunks(2)
+                .skip(1)
+                .all(|pair| allowed.any(|it| *it == path_ai(pair[0])))
+        });
+
+        let mut elements = ElementString::new();
+        let domain_end = match start {
+            Some(start) => {
+                for pair in segments[start..].chunks(2) {
+                    let ai = path_ai(pair[0]);
+                    let value = percent_decode(pair[1])?;
+                    let element = match ai {
+                        "01" if value.len() < 14 => Element::gtin(&value)?,
+                        _ => Element::parse(ai, &value)?,
+                    };
+                    elements.push(element)?;
+                }
+                start
+            }
+            None if segments[1..]
+                .iter()
+                .any(|it| qualifiers(path_ai(it)).is_some()) =>
+            {
+                return Err(invalid(
+                    "path must be primary key followed by key qualifiers in order",
+                ));
+            }
+            None => {
+                let last = segments.len() - 1;
+                if last == 0
+                    || !is_compressed(segments[last])
+                    || segments[1..].iter().any(|it| is_ai(path_ai(it)))
+                {
+                    return Err(invalid("missing primary key"));
+                }
+                let mut reader = BitReader::from_base64url(segments[last]);
+                // zero padding of the last characters is shorter than an AI
+                while reader.remaining() >= 8 {
+                    elements.push(decompress(&mut reader)?)?;
+                }
+                last
+            }
+        };
+        for parameter in query.split('&').filter(|it| !it.is_empty()) {
+            let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            if !is_ai(key) {
+                continue;
+            }
+            elements.push(Element::parse(key, &percent_decode(value)?)?)?;
+        }
+
+        let scheme = &path[..path.len() - rest.len()];
+        DigitalLink::new(elements)?.with_domain(format!(
+            "{}{}",
+            scheme,
+            segments[..domain_end].join("/")
+        ))
+    }
+}
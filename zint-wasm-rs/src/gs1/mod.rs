@@ -0,0 +1,567 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    error::Error,
+    options::{input_mode::InputMode, symbology::Symbology, Options},
+};
+
+pub mod digital_link;
+
+/// Characters allowed in alphanumeric GS1 data fields (GS1 AI encodable
+/// character set 82)
+const CHARSET_82: &str =
+    "!\"%&'()*+,-./0123456789:;<=>?ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz";
+
+/// Maximum length of a GS1 data field
+const MAX_DATA_LEN: usize = 90;
+
+fn invalid(reason: impl Into<String>) -> Error {
+    Error::InvalidGs1 {
+        reason: reason.into(),
+    }
+}
+
+/// Returns GS1 modulo 10 check digit of `digits` (which don't include the
+/// check digit).
+pub(crate) fn gs1_check_digit(digits: &str) -> u8 {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| (digit - b'0') as u32 * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Date in `YYMMDD` format used by GS1 date AIs.
+///
+/// Day may be `0` if only year and month are known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u8,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Creates a date; only the last two digits of `year` are kept.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, Error> {
+        let year = (year % 100) as u8;
+        if !(1..=12).contains(&month) {
+            return Err(invalid(format!(
+                "month must be between 01 and 12; got {:02}",
+                month
+            )));
+        }
+        let days = match month {
+            2 if year.is_multiple_of(4) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if day > days {
+            return Err(invalid(format!(
+                "day must be between 00 and {} for month {:02}; got {:02}",
+                days, month, day
+            )));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Last two digits of the year.
+    pub fn year(&self) -> u8 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Day of month, or `0` if not specified.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}{:02}{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    /// Parses a date in `YYMMDD` format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 6 || !s.bytes().all(|it| it.is_ascii_digit()) {
+            return Err(invalid(format!(
+                "date must be in YYMMDD format; got {:?}",
+                s
+            )));
+        }
+        let part = |i: usize| s[i..i + 2].parse::<u8>().unwrap();
+        Date::new(part(0) as u16, part(2), part(4))
+    }
+}
+
+/// Decimal value of measurement AIs (e.g. `310n`), stored as up to 6 digits
+/// with the position of the decimal point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    value: u32,
+    decimals: u8,
+}
+
+impl Decimal {
+    /// Maximum value of the 6 digit data field
+    pub const MAX_VALUE: u32 = 999_999;
+    /// Maximum number of decimal places
+    pub const MAX_DECIMALS: u8 = 5;
+
+    /// Creates a decimal from digits `value` and number of `decimals`,
+    /// e.g. `Decimal::new(1500, 3)` is `1.500`.
+    pub fn new(value: u32, decimals: u8) -> Result<Self, Error> {
+        if value > Self::MAX_VALUE {
+            return Err(invalid(format!(
+                "measurement must have at most 6 digits; got {}",
+                value
+            )));
+        }
+        if decimals > Self::MAX_DECIMALS {
+            return Err(invalid(format!(
+                "measurement must have at most {} decimal places; got {}",
+                Self::MAX_DECIMALS,
+                decimals
+            )));
+        }
+        Ok(Decimal { value, decimals })
+    }
+
+    /// Digits of the value, without the decimal point.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Number of digits after the decimal point.
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Returns the value as a float.
+    pub fn as_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scale = 10u32.pow(self.decimals as u32);
+        match self.decimals {
+            0 => write!(f, "{}", self.value),
+            decimals => write!(
+                f,
+                "{}.{:0width$}",
+                self.value / scale,
+                self.value % scale,
+                width = decimals as usize
+            ),
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+
+    /// Parses a decimal number (e.g. `"1.500"`), keeping the number of
+    /// decimal places.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (whole, fraction) = s.trim().split_once('.').unwrap_or((s.trim(), ""));
+        let digits = format!("{}{}", whole, fraction);
+        if digits.is_empty() || !digits.bytes().all(|it| it.is_ascii_digit()) {
+            return Err(invalid(format!("invalid measurement {:?}", s)));
+        }
+        let value = digits.parse::<u32>().map_err(|_| {
+            invalid(format!(
+                "measurement must have at most 6 digits; got {:?}",
+                s
+            ))
+        })?;
+        Decimal::new(value, fraction.len().min(u8::MAX as usize) as u8)
+    }
+}
+
+/// GS1 element string: an Application Identifier (AI) along with its data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Element {
+    /// (00) Serial Shipping Container Code, 18 digits
+    Sscc(String),
+    /// (01) Global Trade Item Number, 14 digits
+    Gtin(String),
+    /// (02) GTIN of contained trade items, 14 digits
+    ContentGtin(String),
+    /// (10) Batch or lot number, up to 20 characters
+    Batch(String),
+    /// (11) Production date
+    ProductionDate(Date),
+    /// (13) Packaging date
+    PackagingDate(Date),
+    /// (15) Best before date
+    BestBefore(Date),
+    /// (16) Sell by date
+    SellBy(Date),
+    /// (17) Expiration date
+    Expiry(Date),
+    /// (20) Internal product variant, 2 digits
+    Variant(String),
+    /// (21) Serial number, up to 20 characters
+    Serial(String),
+    /// (30) Variable count of items, up to 8 digits
+    Count(u32),
+    /// (310n) Net weight in kilograms
+    NetWeightKg(Decimal),
+    /// (311n) Length or first dimension in meters
+    LengthM(Decimal),
+    /// (320n) Net weight in pounds
+    NetWeightLb(Decimal),
+    /// (330n) Gross weight of a logistic unit in kilograms
+    GrossWeightKg(Decimal),
+    /// (37) Count of trade items contained in a logistic unit, up to 8 digits
+    ContentCount(u32),
+    /// (400) Customer's purchase order number, up to 30 characters
+    OrderNumber(String),
+    /// (410) Ship to Global Location Number, 13 digits
+    ShipTo(String),
+    /// (414) Global Location Number of a physical location, 13 digits
+    Location(String),
+    /// (422) ISO 3166 numeric country of origin
+    CountryOfOrigin(u16),
+    /// Any other AI with its data field as written in element strings
+    Other { ai: String, value: String },
+}
+
+impl Element {
+    /// Creates a GTIN element from a GTIN-8, GTIN-12, GTIN-13 or GTIN-14,
+    /// padded with leading zeros to 14 digits.
+    pub fn gtin(gtin: &str) -> Result<Self, Error> {
+        if ![8, 12, 13, 14].contains(&gtin.len()) {
+            return Err(invalid(format!(
+                "(01) GTIN must have 8, 12, 13 or 14 digits; got {}",
+                gtin.len()
+            )));
+        }
+        Element::parse("01", &format!("{:0>14}", gtin))
+    }
+
+    /// Parses and validates `value` data field of `ai`.
+    ///
+    /// Known AIs are returned as typed elements with their length, check
+    /// digit and date checked. Other AIs are only checked for length and
+    /// character set.
+    pub fn parse(ai: &str, value: &str) -> Result<Self, Error> {
+        if !(2..=4).contains(&ai.len()) || !ai.bytes().all(|it| it.is_ascii_digit()) {
+            return Err(invalid(format!("AI must have 2 to 4 digits; got {:?}", ai)));
+        }
+        let err = |reason: String| invalid(format!("({}) {}", ai, reason));
+        let numeric = |len: usize, check_digit: bool| -> Result<String, Error> {
+            if value.len() != len || !value.bytes().all(|it| it.is_ascii_digit()) {
+                return Err(err(format!("must have {} digits; got {:?}", len, value)));
+            }
+            if check_digit {
+                let expected = gs1_check_digit(&value[..len - 1]);
+                let actual = value.as_bytes()[len - 1] - b'0';
+                if expected != actual {
+                    return Err(err(format!(
+                        "check digit should be {}; got {}",
+                        expected, actual
+                    )));
+                }
+            }
+            Ok(value.to_string())
+        };
+        let count = || -> Result<u32, Error> {
+            if value.is_empty() || value.len() > 8 || !value.bytes().all(|it| it.is_ascii_digit()) {
+                return Err(err(format!("must have 1 to 8 digits; got {:?}", value)));
+            }
+            Ok(value.parse().unwrap())
+        };
+        let alphanumeric = |max_len: usize| -> Result<String, Error> {
+            if value.is_empty() || value.len() > max_len {
+                return Err(err(format!(
+                    "must have 1 to {} characters; got {}",
+                    max_len,
+                    value.len()
+                )));
+            }
+            if let Some(c) = value.chars().find(|it| !CHARSET_82.contains(*it)) {
+                return Err(err(format!("character {:?} isn't allowed", c)));
+            }
+            Ok(value.to_string())
+        };
+        let date = || Date::from_str(value).map_err(|it| err(strip_reason(it)));
+        let decimal = || -> Result<Decimal, Error> {
+            numeric(6, false)?;
+            Decimal::new(value.parse().unwrap(), ai.as_bytes()[3] - b'0')
+                .map_err(|it| err(strip_reason(it)))
+        };
+
+        Ok(match ai {
+            "00" => Element::Sscc(numeric(18, true)?),
+            "01" => Element::Gtin(numeric(14, true)?),
+            "02" => Element::ContentGtin(numeric(14, true)?),
+            "10" => Element::Batch(alphanumeric(20)?),
+            "11" => Element::ProductionDate(date()?),
+            "13" => Element::PackagingDate(date()?),
+            "15" => Element::BestBefore(date()?),
+            "16" => Element::SellBy(date()?),
+            "17" => Element::Expiry(date()?),
+            "20" => Element::Variant(numeric(2, false)?),
+            "21" => Element::Serial(alphanumeric(20)?),
+            "30" => Element::Count(count()?),
+            "37" => Element::ContentCount(count()?),
+            "400" => Element::OrderNumber(alphanumeric(30)?),
+            "410" => Element::ShipTo(numeric(13, true)?),
+            "414" => Element::Location(numeric(13, true)?),
+            "422" => Element::CountryOfOrigin(numeric(3, false)?.parse().unwrap()),
+            _ if ai.len() == 4 && ["310", "311", "320", "330"].contains(&&ai[..3]) => {
+                match &ai[..3] {
+                    "310" => Element::NetWeightKg(decimal()?),
+                    "311" => Element::LengthM(decimal()?),
+                    "320" => Element::NetWeightLb(decimal()?),
+                    _ => Element::GrossWeightKg(decimal()?),
+                }
+            }
+            _ => Element::Other {
+                ai: ai.to_string(),
+                value: alphanumeric(MAX_DATA_LEN)?,
+            },
+        })
+    }
+
+    /// Returns the Application Identifier (e.g. `"3103"`).
+    pub fn ai(&self) -> String {
+        match self {
+            Element::Sscc(_) => "00".to_string(),
+            Element::Gtin(_) => "01".to_string(),
+            Element::ContentGtin(_) => "02".to_string(),
+            Element::Batch(_) => "10".to_string(),
+            Element::ProductionDate(_) => "11".to_string(),
+            Element::PackagingDate(_) => "13".to_string(),
+            Element::BestBefore(_) => "15".to_string(),
+            Element::SellBy(_) => "16".to_string(),
+            Element::Expiry(_) => "17".to_string(),
+            Element::Variant(_) => "20".to_string(),
+            Element::Serial(_) => "21".to_string(),
+            Element::Count(_) => "30".to_string(),
+            Element::NetWeightKg(value) => format!("310{}", value.decimals),
+            Element::LengthM(value) => format!("311{}", value.decimals),
+            Element::NetWeightLb(value) => format!("320{}", value.decimals),
+            Element::GrossWeightKg(value) => format!("330{}", value.decimals),
+            Element::ContentCount(_) => "37".to_string(),
+            Element::OrderNumber(_) => "400".to_string(),
+            Element::ShipTo(_) => "410".to_string(),
+            Element::Location(_) => "414".to_string(),
+            Element::CountryOfOrigin(_) => "422".to_string(),
+            Element::Other { ai, .. } => ai.clone(),
+        }
+    }
+
+    /// Returns the data field as written in element strings.
+    pub fn value(&self) -> String {
+        match self {
+            Element::Sscc(value)
+            | Element::Gtin(value)
+            | Element::ContentGtin(value)
+            | Element::Batch(value)
+            | Element::Variant(value)
+            | Element::Serial(value)
+            | Element::OrderNumber(value)
+            | Element::ShipTo(value)
+            | Element::Location(value)
+            | Element::Other { value, .. } => value.clone(),
+            Element::ProductionDate(date)
+            | Element::PackagingDate(date)
+            | Element::BestBefore(date)
+            | Element::SellBy(date)
+            | Element::Expiry(date) => date.to_string(),
+            Element::Count(count) | Element::ContentCount(count) => count.to_string(),
+            Element::NetWeightKg(value)
+            | Element::LengthM(value)
+            | Element::NetWeightLb(value)
+            | Element::GrossWeightKg(value) => format!("{:06}", value.value),
+            Element::CountryOfOrigin(country) => format!("{:03}", country),
+        }
+    }
+
+    /// Returns an error if the element data isn't valid for its AI.
+    pub fn validate(&self) -> Result<(), Error> {
+        let parsed = Element::parse(&self.ai(), &self.value())?;
+        if let Element::Other { ai, .. } = self {
+            if !matches!(parsed, Element::Other { .. }) {
+                return Err(invalid(format!(
+                    "({}) has a typed element which should be used instead",
+                    ai
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Removes `invalid GS1 data; ` prefix so the reason can be reworded.
+fn strip_reason(error: Error) -> String {
+    match error {
+        Error::InvalidGs1 { reason } => reason,
+        other => other.to_string(),
+    }
+}
+
+/// Format of AIs in element strings passed to Zint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AiFormat {
+    /// AIs in square brackets, e.g. `[01]09501101530003`
+    #[default]
+    Brackets,
+    /// AIs in parentheses, e.g. `(01)09501101530003`, with parentheses in
+    /// data escaped
+    Parentheses,
+}
+
+/// Concatenated GS1 element strings, validated as they're added.
+///
+/// ```
+/// # use zint_wasm_rs::gs1::{AiFormat, Date, Element, ElementString};
+/// let elements = ElementString::new()
+///     .with(Element::gtin("9501101530003")?)?
+///     .with(Element::Expiry(Date::new(2025, 1, 1)?))?
+///     .with(Element::Batch("AB(12)".to_string()))?;
+/// assert_eq!(elements.to_string(), "[01]09501101530003[17]250101[10]AB(12)");
+/// assert_eq!(
+///     elements.format(AiFormat::Parentheses),
+///     r"(01)09501101530003(17)250101(10)AB\(12\)"
+/// );
+/// # Ok::<(), zint_wasm_rs::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ElementString {
+    elements: Vec<Element>,
+}
+
+impl ElementString {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `element`, returning an error if it's invalid or its AI is
+    /// already present.
+    pub fn push(&mut self, element: Element) -> Result<(), Error> {
+        element.validate()?;
+        let ai = element.ai();
+        if self.elements.iter().any(|it| it.ai() == ai) {
+            return Err(invalid(format!("({}) is present more than once", ai)));
+        }
+        self.elements.push(element);
+        Ok(())
+    }
+
+    /// Adds `element`, see [`push`](ElementString::push).
+    pub fn with(mut self, element: Element) -> Result<Self, Error> {
+        self.push(element)?;
+        Ok(self)
+    }
+
+    /// Elements in order they were added.
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Returns element with `ai`, if present.
+    pub fn get(&self, ai: &str) -> Option<&Element> {
+        self.elements.iter().find(|it| it.ai() == ai)
+    }
+
+    /// Returns the element string with AIs written in `format`.
+    pub fn format(&self, format: AiFormat) -> String {
+        let mut result = String::new();
+        for element in &self.elements {
+            let value = element.value();
+            match format {
+                AiFormat::Brackets => {
+                    result.push_str(&format!("[{}]{}", element.ai(), value));
+                }
+                AiFormat::Parentheses => {
+                    result.push_str(&format!("({})", element.ai()));
+                    for c in value.chars() {
+                        if c == '(' || c == ')' {
+                            result.push('\\');
+                        }
+                        result.push(c);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns input mode for encoding the element string in `format`.
+    pub fn input_mode(&self, format: AiFormat) -> InputMode {
+        match format {
+            AiFormat::Brackets => InputMode::GS1,
+            AiFormat::Parentheses if self.format(format).contains('\\') => {
+                InputMode::GS1 | InputMode::GS1_PARENTHESES | InputMode::ESCAPE
+            }
+            AiFormat::Parentheses => InputMode::GS1 | InputMode::GS1_PARENTHESES,
+        }
+    }
+
+    /// Returns options for encoding the element string in `format` with
+    /// `symbology` (e.g. GS1-128, DataBar Expanded, Data Matrix or QR Code).
+    pub fn options(&self, symbology: Symbology, format: AiFormat) -> Result<Options, Error> {
+        if self.elements.is_empty() {
+            return Err(invalid("element string is empty"));
+        }
+        Options::builder(symbology)
+            .input_mode(self.input_mode(format))
+            .build()
+    }
+}
+
+impl Display for ElementString {
+    /// Formats the element string with AIs in square brackets.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format(AiFormat::Brackets))
+    }
+}
+
+impl FromStr for ElementString {
+    type Err = Error;
+
+    /// Parses an element string with AIs in square brackets or parentheses
+    /// (with parentheses in data escaped by `\`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (open, close) = match s.chars().next() {
+            Some('[') => ('[', ']'),
+            Some('(') => ('(', ')'),
+            _ => return Err(invalid("element string must start with [ or (")),
+        };
+
+        let mut result = ElementString::new();
+        let mut chars = s.chars().peekable();
+        while chars.next().is_some() {
+            let ai: String = chars.by_ref().take_while(|it| *it != close).collect();
+            let mut value = String::new();
+            while let Some(c) = chars.next_if(|it| *it != open) {
+                match c {
+                    '\\' if open == '(' => match chars.next() {
+                        Some(escaped @ ('(' | ')' | '\\')) => value.push(escaped),
+                        _ => return Err(invalid("invalid escape sequence")),
+                    },
+                    c => value.push(c),
+                }
+            }
+            result.push(Element::parse(&ai, &value)?)?;
+        }
+        Ok(result)
+    }
+}
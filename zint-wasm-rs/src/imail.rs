@@ -0,0 +1,176 @@
+//! USPS Intelligent Mail barcode fields.
+//!
+//! ```no_run
+//! # use zint_wasm_rs::{imail::IntelligentMail, symbol::Symbol};
+//! let imail = IntelligentMail::new(1, 234, "567094", "987654321")?
+//!     .with_routing_code("01234567891")?;
+//! assert_eq!(imail.to_string(), "0123456709498765432101234567891");
+//! let mut symbol = Symbol::new(&imail.options()?);
+//! let svg = symbol.encode_svg(&imail.to_string())?;
+//! # Ok::<(), zint_wasm_rs::error::Error>(())
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    error::Error,
+    options::{symbology::Symbology, Options},
+};
+
+/// Length of the tracking code (barcode ID, service type, mailer ID and
+/// serial number)
+const TRACKING_CODE_LEN: usize = 20;
+
+/// Valid routing code lengths
+const ROUTING_CODE_LENS: [usize; 4] = [0, 5, 9, 11];
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidIntelligentMail { reason }
+}
+
+fn check_digits(which: &str, value: &str, lens: &[usize]) -> Result<(), Error> {
+    if !lens.contains(&value.len()) || !value.bytes().all(|it| it.is_ascii_digit()) {
+        let (last, rest) = lens.split_last().unwrap();
+        let lens = match rest {
+            [] => last.to_string(),
+            _ => {
+                let rest: Vec<_> = rest.iter().map(|it| it.to_string()).collect();
+                format!("{} or {}", rest.join(", "), last)
+            }
+        };
+        return Err(invalid(format!(
+            "{} must have {} digits; got {:?}",
+            which, lens, value
+        )));
+    }
+    Ok(())
+}
+
+/// USPS Intelligent Mail barcode data.
+///
+/// Mailer IDs starting with `9` have 9 digits and are followed by a 6 digit
+/// serial number, other mailer IDs have 6 digits and are followed by a 9
+/// digit serial number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntelligentMail {
+    barcode_id: u8,
+    service_type: u16,
+    mailer_id: String,
+    serial: String,
+    routing_code: String,
+}
+
+impl IntelligentMail {
+    /// Creates Intelligent Mail data without routing code.
+    ///
+    /// Second digit of `barcode_id` must be `0`-`4` and `service_type` must
+    /// have up to 3 digits.
+    pub fn new(
+        barcode_id: u8,
+        service_type: u16,
+        mailer_id: &str,
+        serial: &str,
+    ) -> Result<Self, Error> {
+        if barcode_id > 94 || barcode_id % 10 > 4 {
+            return Err(invalid(format!(
+                "barcode ID must have 2 digits with second digit 0-4; got {:02}",
+                barcode_id
+            )));
+        }
+        if service_type > 999 {
+            return Err(invalid(format!(
+                "service type must have 3 digits; got {}",
+                service_type
+            )));
+        }
+        let mailer_id_len = if mailer_id.starts_with('9') { 9 } else { 6 };
+        check_digits(
+            if mailer_id_len == 9 {
+                "mailer ID starting with 9"
+            } else {
+                "mailer ID"
+            },
+            mailer_id,
+            &[mailer_id_len],
+        )?;
+        check_digits("serial number", serial, &[15 - mailer_id_len])?;
+        Ok(IntelligentMail {
+            barcode_id,
+            service_type,
+            mailer_id: mailer_id.to_string(),
+            serial: serial.to_string(),
+            routing_code: String::new(),
+        })
+    }
+
+    /// Sets routing code, which is a 5, 9 or 11 digit ZIP code, or no
+    /// routing code if empty.
+    pub fn with_routing_code(mut self, routing_code: &str) -> Result<Self, Error> {
+        check_digits("routing code", routing_code, &ROUTING_CODE_LENS)?;
+        self.routing_code = routing_code.to_string();
+        Ok(self)
+    }
+
+    pub fn barcode_id(&self) -> u8 {
+        self.barcode_id
+    }
+
+    pub fn service_type(&self) -> u16 {
+        self.service_type
+    }
+
+    pub fn mailer_id(&self) -> &str {
+        &self.mailer_id
+    }
+
+    pub fn serial(&self) -> &str {
+        &self.serial
+    }
+
+    /// Returns routing ZIP code, which is empty if there's none.
+    pub fn routing_code(&self) -> &str {
+        &self.routing_code
+    }
+
+    /// Returns Intelligent Mail barcode options.
+    pub fn options(&self) -> Result<Options, Error> {
+        Options::builder(Symbology::USPSIMail).build()
+    }
+}
+
+impl Display for IntelligentMail {
+    /// Writes 20, 25, 29 or 31 digit data string.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}{:03}{}{}{}",
+            self.barcode_id, self.service_type, self.mailer_id, self.serial, self.routing_code
+        )
+    }
+}
+
+impl FromStr for IntelligentMail {
+    type Err = Error;
+
+    /// Parses a 20, 25, 29 or 31 digit data string, optionally with routing
+    /// code separated by `-`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tracking, routing_code) = match s.split_once('-') {
+            Some(parts) => parts,
+            None if s.len() >= TRACKING_CODE_LEN && s.is_char_boundary(TRACKING_CODE_LEN) => {
+                s.split_at(TRACKING_CODE_LEN)
+            }
+            None => (s, ""),
+        };
+        check_digits("tracking code", tracking, &[TRACKING_CODE_LEN])?;
+        let mailer_id_len = if tracking.as_bytes()[5] == b'9' { 9 } else { 6 };
+        let (mailer_id, serial) = tracking[5..].split_at(mailer_id_len);
+        IntelligentMail::new(
+            tracking[..2].parse().unwrap(),
+            tracking[2..5].parse().unwrap(),
+            mailer_id,
+            serial,
+        )?
+        .with_routing_code(routing_code)
+    }
+}
@@ -1,6 +1,26 @@
+#[cfg(all(
+    feature = "raster",
+    not(any(
+        feature = "png",
+        feature = "bmp",
+        feature = "gif",
+        feature = "pcx",
+        feature = "tif"
+    ))
+))]
+compile_error!(
+    "`raster` feature doesn't enable any output format; enable one of `png`, `bmp`, `gif`, `pcx` or `tif` instead"
+);
+
+pub mod check_digit;
 pub mod error;
+pub mod gs1;
+pub mod imail;
+pub mod matrix;
+pub mod maxicode;
 pub mod options;
 pub mod symbol;
+pub mod vector;
 
 pub(crate) mod util {
     use std::ffi::CString;
@@ -26,4 +46,17 @@ pub(crate) mod util {
             dest[i] = *v
         }
     }
+
+    /// Reads a NUL-terminated C char buffer into a Rust string.
+    ///
+    /// Invalid UTF-8 sequences are replaced and a missing NUL terminator
+    /// reads the whole buffer.
+    pub fn cstr_to_string(src: &[::std::os::raw::c_char]) -> String {
+        let bytes: &[u8] = unsafe {
+            // Safety: c_char and u8 have the same size and alignment
+            std::slice::from_raw_parts(src.as_ptr() as *const u8, src.len())
+        };
+        let len = bytes.iter().position(|it| *it == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..len]).to_string()
+    }
 }
@@ -0,0 +1,110 @@
+use zint_wasm_sys::zint_symbol;
+
+use crate::{options::symbology::Symbology, vector::VectorColor};
+
+/// Owned copy of the module grid of an encoded symbol (`encoded_data` and
+/// `row_height` fields).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleMatrix {
+    rows: usize,
+    width: usize,
+    row_heights: Vec<f32>,
+    /// Row-major module values; `0` for unset modules, Ultracode color
+    /// (`1`-`8`) or `1` for set modules otherwise.
+    modules: Vec<u8>,
+    is_colour: bool,
+}
+
+impl ModuleMatrix {
+    /// Copies module data out of an encoded `symbol`.
+    pub(crate) fn from_symbol(symbol: &zint_symbol) -> Self {
+        let rows = (symbol.rows.max(0) as usize).min(symbol.encoded_data.len());
+        let is_colour = symbol.symbology == Symbology::Ultra.id();
+        let max_width = if is_colour {
+            symbol.encoded_data[0].len()
+        } else {
+            symbol.encoded_data[0].len() * 8
+        };
+        let width = (symbol.width.max(0) as usize).min(max_width);
+
+        let mut modules = Vec::with_capacity(rows * width);
+        for row in &symbol.encoded_data[..rows] {
+            for col in 0..width {
+                modules.push(if is_colour {
+                    row[col]
+                } else {
+                    (row[col >> 3] >> (col & 0x07)) & 1
+                });
+            }
+        }
+
+        Self {
+            rows,
+            width,
+            row_heights: symbol.row_height[..rows].to_vec(),
+            modules,
+            is_colour,
+        }
+    }
+
+    /// Number of rows in the symbol.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Width of the symbol in modules.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Heights of rows in X-dimensions.
+    pub fn row_heights(&self) -> &[f32] {
+        &self.row_heights
+    }
+
+    /// Height of `row` in X-dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    pub fn row_height(&self, row: usize) -> f32 {
+        self.row_heights[row]
+    }
+
+    /// Returns whether module at `row` and `col` is set (dark).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `col` are out of bounds.
+    pub fn is_set(&self, row: usize, col: usize) -> bool {
+        self.module(row, col) != 0
+    }
+
+    /// Returns color of module at `row` and `col` for Ultracode symbols.
+    ///
+    /// Returns `None` for unset modules and modules of other symbologies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `col` are out of bounds.
+    pub fn color(&self, row: usize, col: usize) -> Option<VectorColor> {
+        match self.module(row, col) {
+            colour @ 1..=8 if self.is_colour => Some(VectorColor::from_rect_colour(colour as i32)),
+            _ => None,
+        }
+    }
+
+    /// Iterates over rows of the matrix, yielding modules of each row.
+    pub fn iter_rows(&self) -> impl Iterator<Item = impl Iterator<Item = bool> + '_> + '_ {
+        self.modules
+            .chunks(self.width.max(1))
+            .take(self.rows)
+            .map(|row| row.iter().map(|it| *it != 0))
+    }
+
+    fn module(&self, row: usize, col: usize) -> u8 {
+        assert!(row < self.rows, "row {} out of bounds", row);
+        assert!(col < self.width, "column {} out of bounds", col);
+        self.modules[row * self.width + col]
+    }
+}
@@ -0,0 +1,187 @@
+//! Structured carrier messages for MaxiCode modes 2 and 3.
+//!
+//! ```no_run
+//! # use zint_wasm_rs::{maxicode::CarrierMessage, symbol::Symbol};
+//! let message = CarrierMessage::new("152382802", 840, 1)?
+//!     .with_secondary("1Z00004951\x1DUPSN\x1D06X610\x1D159\x1D1234567\x1D1/1\x1D\x1DY\x1D\x1D\x1DUT\x1E\x04");
+//! let mut symbol = Symbol::new(&message.options()?);
+//! let svg = symbol.encode_svg(&message.data())?;
+//! # Ok::<(), zint_wasm_rs::error::Error>(())
+//! ```
+
+use crate::{
+    error::Error,
+    options::{symbology::Symbology, symbology_options::MaxiCodeOptions, Options},
+};
+
+/// Maximum length of a numeric (mode 2) postcode
+const MAX_NUMERIC_POSTCODE_LEN: usize = 9;
+
+/// Maximum length of an alphanumeric (mode 3) postcode
+const MAX_ALPHANUMERIC_POSTCODE_LEN: usize = 6;
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidMaxiCodeMessage { reason }
+}
+
+/// MaxiCode carrier message with a structured primary message.
+///
+/// Numeric postcodes are encoded in mode 2 and alphanumeric ones in mode 3.
+/// Secondary message is prefixed with the UPS Structured Carrier Message
+/// header unless it's disabled with
+/// [`with_scm_version`](CarrierMessage::with_scm_version).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CarrierMessage {
+    postcode: String,
+    country: u16,
+    service_class: u16,
+    scm_version: Option<u8>,
+    secondary: String,
+}
+
+impl CarrierMessage {
+    /// Structured Carrier Message header, followed by a 2 digit version
+    pub const SCM_HEADER: &'static str = "[)>\x1E01\x1D";
+    /// Default Structured Carrier Message version
+    pub const DEFAULT_SCM_VERSION: u8 = 96;
+
+    /// Creates a carrier message with `postcode`, ISO 3166 numeric
+    /// `country` code and `service_class`.
+    ///
+    /// Postcode must have up to 9 digits or up to 6 uppercase letters,
+    /// digits and spaces.
+    pub fn new(postcode: &str, country: u16, service_class: u16) -> Result<Self, Error> {
+        if postcode.is_empty() {
+            return Err(invalid("postcode is empty".to_string()));
+        }
+        if postcode.bytes().all(|it| it.is_ascii_digit()) {
+            if postcode.len() > MAX_NUMERIC_POSTCODE_LEN {
+                return Err(invalid(format!(
+                    "numeric postcode must have up to {} digits; got {}",
+                    MAX_NUMERIC_POSTCODE_LEN,
+                    postcode.len()
+                )));
+            }
+        } else {
+            if let Some(c) = postcode
+                .chars()
+                .find(|it| !it.is_ascii_uppercase() && !it.is_ascii_digit() && *it != ' ')
+            {
+                return Err(invalid(format!(
+                    "alphanumeric postcode can only contain A-Z, 0-9 and space; got {:?}",
+                    c
+                )));
+            }
+            if postcode.len() > MAX_ALPHANUMERIC_POSTCODE_LEN {
+                return Err(invalid(format!(
+                    "alphanumeric postcode must have up to {} characters; got {}",
+                    MAX_ALPHANUMERIC_POSTCODE_LEN,
+                    postcode.len()
+                )));
+            }
+        }
+        if country > 999 {
+            return Err(invalid(format!(
+                "country code must have 3 digits; got {}",
+                country
+            )));
+        }
+        if service_class > 999 {
+            return Err(invalid(format!(
+                "service class must have 3 digits; got {}",
+                service_class
+            )));
+        }
+        Ok(CarrierMessage {
+            postcode: postcode.to_string(),
+            country,
+            service_class,
+            scm_version: Some(Self::DEFAULT_SCM_VERSION),
+            secondary: String::new(),
+        })
+    }
+
+    /// Sets Structured Carrier Message version (`0`-`99`), or omits the
+    /// header if `None`.
+    pub fn with_scm_version(mut self, version: Option<u8>) -> Result<Self, Error> {
+        if let Some(version) = version.filter(|it| *it > 99) {
+            return Err(invalid(format!(
+                "SCM version must have 2 digits; got {}",
+                version
+            )));
+        }
+        self.scm_version = version;
+        Ok(self)
+    }
+
+    /// Sets secondary message, which follows the SCM header.
+    ///
+    /// UPS messages consist of fields separated by `GS` (`\x1D`) and end
+    /// with `RS` `EOT` (`\x1E\x04`).
+    pub fn with_secondary(mut self, secondary: impl Into<String>) -> Self {
+        self.secondary = secondary.into();
+        self
+    }
+
+    pub fn postcode(&self) -> &str {
+        &self.postcode
+    }
+
+    pub fn country(&self) -> u16 {
+        self.country
+    }
+
+    pub fn service_class(&self) -> u16 {
+        self.service_class
+    }
+
+    pub fn scm_version(&self) -> Option<u8> {
+        self.scm_version
+    }
+
+    pub fn secondary(&self) -> &str {
+        &self.secondary
+    }
+
+    /// Returns whether the postcode is numeric.
+    pub fn is_numeric(&self) -> bool {
+        self.postcode.bytes().all(|it| it.is_ascii_digit())
+    }
+
+    /// Returns MaxiCode mode (`2` for numeric and `3` for alphanumeric
+    /// postcodes).
+    pub fn mode(&self) -> i32 {
+        if self.is_numeric() {
+            2
+        } else {
+            3
+        }
+    }
+
+    /// Returns primary message, consisting of postcode, country code and
+    /// service class.
+    pub fn primary(&self) -> String {
+        format!(
+            "{}{:03}{:03}",
+            self.postcode, self.country, self.service_class
+        )
+    }
+
+    /// Returns secondary message data, prefixed with SCM header.
+    pub fn data(&self) -> String {
+        match self.scm_version {
+            Some(version) => format!("{}{:02}{}", Self::SCM_HEADER, version, self.secondary),
+            None => self.secondary.clone(),
+        }
+    }
+
+    /// Returns MaxiCode options with primary message and mode.
+    pub fn options(&self) -> Result<Options, Error> {
+        Options::builder(Symbology::MaxiCode)
+            .primary(self.primary())
+            .symbology_options(MaxiCodeOptions {
+                mode: Some(self.mode()),
+            })
+            .build()
+    }
+}
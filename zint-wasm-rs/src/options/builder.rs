@@ -0,0 +1,241 @@
+use std::ops::RangeInclusive;
+
+use crate::error::Error;
+
+use super::{
+    capability::CapabilityFlags,
+    check_scaling,
+    color::Color,
+    input_mode::InputMode,
+    option3::{DataMatrixOption, Option3, QRMask, QRMatrixOption, UltracodeOption},
+    output_options::OutputOptions,
+    rotation::Rotation,
+    structured_append::StructuredAppend,
+    symbology::Symbology,
+    symbology_options::{check_range, version_range, QrEcc, SymbologyOptions},
+    Options,
+};
+
+/// Builder of [`Options`] with typed symbology-specific options.
+///
+/// Combinations of options are validated against the symbology by
+/// [`build`](OptionsBuilder::build).
+#[derive(Debug, Clone)]
+pub struct OptionsBuilder {
+    options: Options,
+    ecc: Option<QrEcc>,
+    version: Option<i32>,
+    mask: Option<QRMask>,
+    full_multibyte: bool,
+    data_matrix: Option<DataMatrixOption>,
+    ultra_compression: bool,
+}
+
+impl Options {
+    /// Creates a builder of options for `symbology`.
+    pub fn builder(symbology: Symbology) -> OptionsBuilder {
+        OptionsBuilder {
+            options: Options::with_symbology(symbology),
+            ecc: None,
+            version: None,
+            mask: None,
+            full_multibyte: false,
+            data_matrix: None,
+            ultra_compression: false,
+        }
+    }
+}
+
+macro_rules! setters {
+    ($($(#[$attr: meta])* $name: ident: $ty: ty,)+) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(mut self, $name: impl Into<$ty>) -> Self {
+                self.options.$name = Some($name.into());
+                self
+            }
+        )+
+    };
+}
+
+impl OptionsBuilder {
+    setters! {
+        /// Sets barcode height in X-dimensions.
+        height: f32,
+        /// Sets scale factor.
+        scale: f32,
+        /// Sets X-dimension in millimeters.
+        x_dimension_mm: f32,
+        /// Sets output resolution in dots per millimeter.
+        dpmm: f32,
+        /// Sets width in X-dimensions of whitespace to left & right.
+        whitespace_width: i32,
+        /// Sets height in X-dimensions of whitespace above & below.
+        whitespace_height: i32,
+        /// Sets size of border in X-dimensions.
+        border_width: i32,
+        /// Sets output options.
+        output_options: OutputOptions,
+        /// Sets foreground color.
+        fg_color: Color,
+        /// Sets background color.
+        bg_color: Color,
+        /// Sets primary message data (MaxiCode, Composite).
+        primary: String,
+        /// Shows or hides Human Readable Text.
+        show_hrt: bool,
+        /// Sets encoding of input data.
+        input_mode: InputMode,
+        /// Sets Extended Channel Interpretation.
+        eci: i32,
+        /// Sets size of dots in dotty mode.
+        dot_size: f32,
+        /// Sets gap between barcode and text in X-dimensions.
+        text_gap: f32,
+        /// Sets height in X-dimensions that EAN/UPC guard bars descend.
+        guard_descent: f32,
+        /// Sets clockwise rotation of the output.
+        rotation: Rotation,
+        /// Sets position in a Structured Append sequence.
+        structured_append: StructuredAppend,
+        /// Sets typed symbology specific options.
+        symbology_options: SymbologyOptions,
+    }
+
+    /// Sets error correction level (QR Code, Micro QR, rMQR).
+    pub fn ecc(mut self, ecc: QrEcc) -> Self {
+        self.ecc = Some(ecc);
+        self
+    }
+
+    /// Sets symbol version or size (`option_2`).
+    ///
+    /// Valid range depends on symbology, e.g. `1`-`40` for QR Code or
+    /// `1`-`48` for Data Matrix.
+    pub fn version(mut self, version: i32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Selects mask (QR Code, Micro QR, Han Xin, UPNQR).
+    pub fn mask(mut self, mask: QRMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Increases density of non-ASCII data (QR Code, Han Xin, Grid Matrix).
+    pub fn full_multibyte(mut self) -> Self {
+        self.full_multibyte = true;
+        self
+    }
+
+    /// Sets Data Matrix size selection option.
+    pub fn data_matrix(mut self, option: DataMatrixOption) -> Self {
+        self.data_matrix = Some(option);
+        self
+    }
+
+    /// Enables Ultracode compression.
+    pub fn ultra_compression(mut self) -> Self {
+        self.ultra_compression = true;
+        self
+    }
+
+    /// Validates configured options against the symbology.
+    pub fn build(self) -> Result<Options, Error> {
+        let mut options = self.options;
+        let symbology = options.symbology;
+        let incompatible = |which| Error::IncompatibleOption { which, symbology };
+        let out_of_range = |which, value: i32, range: RangeInclusive<i32>| Error::OutOfRange {
+            which,
+            value: value as f32,
+            range: *range.start() as f32..=*range.end() as f32,
+            symbology,
+        };
+
+        if let Some(ecc) = self.ecc {
+            if !matches!(
+                symbology,
+                Symbology::QRCode | Symbology::HIBCQR | Symbology::MicroQR | Symbology::RMQR
+            ) {
+                return Err(incompatible("ecc"));
+            }
+            if !ecc.supported_by(symbology) {
+                return Err(Error::UnsupportedEcc { ecc, symbology });
+            }
+            options.option_1 = Some(ecc as i32);
+        }
+
+        if let Some(version) = self.version {
+            let range = version_range(symbology).ok_or_else(|| incompatible("version"))?;
+            check_range("version", version, range, symbology)?;
+            options.option_2 = Some(version);
+        }
+
+        let mut option_3 = None;
+        if self.mask.is_some() || self.full_multibyte {
+            let mut qr_matrix = QRMatrixOption::empty();
+            if let Some(mask) = self.mask {
+                let max = match symbology {
+                    Symbology::QRCode | Symbology::HIBCQR | Symbology::UPNQR => QRMask::Mask7,
+                    Symbology::MicroQR | Symbology::HanXin => QRMask::Mask3,
+                    _ => return Err(incompatible("mask")),
+                };
+                if mask as u32 > max as u32 {
+                    return Err(out_of_range("mask", mask as i32, 0..=max as i32));
+                }
+                qr_matrix |= QRMatrixOption::from(mask);
+            }
+            if self.full_multibyte {
+                if !symbology.supports(CapabilityFlags::FullMultibyte) {
+                    return Err(Error::UnsupportedCapability(CapabilityFlags::FullMultibyte));
+                }
+                qr_matrix |= QRMatrixOption::FULL_MULITIBYTE;
+            }
+            option_3 = Some(Option3::from(qr_matrix));
+        }
+        if let Some(data_matrix) = self.data_matrix {
+            if !matches!(symbology, Symbology::DataMatrix | Symbology::HIBCDM) {
+                return Err(incompatible("data_matrix"));
+            }
+            option_3 = Some(Option3::from(data_matrix));
+        }
+        if self.ultra_compression {
+            if symbology != Symbology::Ultra {
+                return Err(incompatible("ultra_compression"));
+            }
+            option_3 = Some(Option3::from(UltracodeOption::Compression));
+        }
+        if option_3.is_some() {
+            options.option_3 = option_3;
+        }
+
+        if let Some(ref symbology_options) = options.symbology_options {
+            symbology_options.check(symbology)?;
+        }
+        check_scaling(symbology, options.x_dimension_mm, options.dpmm)?;
+
+        if let Some(input_mode) = options.input_mode {
+            if let Some(failiure) = input_mode.validate() {
+                return Err(Error::InvalidInputMode(failiure));
+            }
+            if input_mode.contains(InputMode::GS1) && !symbology.supports(CapabilityFlags::Gs1) {
+                return Err(Error::UnsupportedCapability(CapabilityFlags::Gs1));
+            }
+        }
+        if options.eci.is_some_and(|eci| eci != 0) && !symbology.supports(CapabilityFlags::Eci) {
+            return Err(Error::UnsupportedCapability(CapabilityFlags::Eci));
+        }
+        if let Some(ref structured_append) = options.structured_append {
+            let max_count = StructuredAppend::max_count(symbology)
+                .ok_or(Error::UnsupportedCapability(CapabilityFlags::StructApp))?;
+            if structured_append.count() as usize > max_count {
+                return Err(Error::InvalidStructuredAppend {
+                    reason: "count out of range for symbology",
+                });
+            }
+        }
+
+        Ok(options)
+    }
+}
@@ -61,3 +61,59 @@ impl From<CapabilityFlags> for i32 {
         .unwrap()
     }
 }
+
+bitflags::bitflags! {
+    /// Set of capabilities supported by a symbology (`ZBarcode_Cap()` result)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct CapabilitySet: u32 {
+        /// Prints Human Readable Text
+        const HRT = ZINT_CAP_HRT;
+        /// Is stackable
+        const STACKABLE = ZINT_CAP_STACKABLE;
+        /// Is EAN/UPC
+        const EAN_UPC = ZINT_CAP_EANUPC;
+        /// Legacy
+        const EXTENDABLE = ZINT_CAP_EXTENDABLE;
+        /// Can have composite data
+        const COMPOSITE = ZINT_CAP_COMPOSITE;
+        /// Supports Extended Channel Interpretations
+        const ECI = ZINT_CAP_ECI;
+        /// Supports GS1 data
+        const GS1 = ZINT_CAP_GS1;
+        /// Can be output as dots
+        const DOTTY = ZINT_CAP_DOTTY;
+        /// Has default quiet zones
+        const QUIET_ZONES = ZINT_CAP_QUIET_ZONES;
+        /// Has fixed width-to-height (aspect) ratio
+        const FIXED_RATIO = ZINT_CAP_FIXED_RATIO;
+        /// Supports Reader Initialisation
+        const READER_INIT = ZINT_CAP_READER_INIT;
+        /// Supports full-multibyte option
+        const FULL_MULTIBYTE = ZINT_CAP_FULL_MULTIBYTE;
+        /// Is mask selectable
+        const MASK = ZINT_CAP_MASK;
+        /// Supports Structured Append
+        const STRUCT_APP = ZINT_CAP_STRUCTAPP;
+        /// Has compliant height
+        const COMPLIANT_HEIGHT = ZINT_CAP_COMPLIANT_HEIGHT;
+    }
+}
+
+impl CapabilitySet {
+    /// Queries Zint for capabilities of symbology with `symbol_id`.
+    pub(crate) fn query(symbol_id: i32) -> Self {
+        let result = unsafe { ZBarcode_Cap(symbol_id, Self::all().bits()) };
+        Self::from_bits_truncate(result)
+    }
+
+    /// Returns whether the set contains `capability`.
+    pub fn supports(&self, capability: CapabilityFlags) -> bool {
+        self.contains(capability.into())
+    }
+}
+
+impl From<CapabilityFlags> for CapabilitySet {
+    fn from(value: CapabilityFlags) -> Self {
+        CapabilitySet::from_bits_retain(i32::from(value) as u32)
+    }
+}
@@ -0,0 +1,526 @@
+use std::{fmt::Display, str::FromStr};
+
+use zint_wasm_sys::{DM_DMRE, DM_ISO_144, DM_SQUARE, ZINT_FULL_MULTIBYTE};
+
+use crate::error::Error;
+
+use super::{
+    color::Color,
+    input_mode::InputMode,
+    option3::{DataMatrixOption, Option3, QRMatrixOption},
+    output_options::OutputOptions,
+    rotation::Rotation,
+    structured_append::StructuredAppend,
+    symbology::{Symbology, SymbologyCategory},
+    Options,
+};
+
+const MM_PER_INCH: f32 = 25.4;
+
+/// Long options which take a value (`--name=value` or `--name value`)
+const VALUED: &[&str] = &[
+    "addongap",
+    "barcode",
+    "bg",
+    "bgcolor",
+    "bgcolour",
+    "border",
+    "cols",
+    "dotsize",
+    "eci",
+    "fg",
+    "fgcolor",
+    "fgcolour",
+    "guarddescent",
+    "height",
+    "mask",
+    "mode",
+    "primary",
+    "rotate",
+    "rows",
+    "scale",
+    "scalexdimdp",
+    "scmvv",
+    "secure",
+    "structapp",
+    "textgap",
+    "vers",
+    "vwhitesp",
+    "whitesp",
+];
+
+/// Long options which don't take a value, other than input mode and output
+/// option flags
+const FLAGS: &[&str] = &[
+    "binary",
+    "dmiso144",
+    "dmre",
+    "fullmultibyte",
+    "gs1",
+    "nobackground",
+    "notext",
+    "reverse",
+    "square",
+];
+
+/// Flags setting input mode options
+const INPUT_FLAGS: &[(&str, InputMode)] = &[
+    ("esc", InputMode::ESCAPE),
+    ("extraesc", InputMode::EXTRA_ESCAPE),
+    ("fast", InputMode::FAST),
+    ("gs1nocheck", InputMode::GS1_NO_CHECK),
+    ("gs1parens", InputMode::GS1_PARENTHESES),
+    ("heightperrow", InputMode::HEIGHT_PER_ROW),
+];
+
+/// Flags setting output options
+const OUTPUT_FLAGS: &[(&str, OutputOptions)] = &[
+    ("bind", OutputOptions::BARCODE_BIND),
+    ("bindtop", OutputOptions::BARCODE_BIND_TOP),
+    ("bold", OutputOptions::BOLD_TEXT),
+    ("box", OutputOptions::BARCODE_BOX),
+    ("cmyk", OutputOptions::CMYK_COLOR),
+    ("compliantheight", OutputOptions::COMPLIANT_HEIGHT),
+    ("dotty", OutputOptions::BARCODE_DOTTY_MODE),
+    ("embedfont", OutputOptions::EMBED_VECTOR_FONT),
+    ("gssep", OutputOptions::GS1_GS_SEPARATOR),
+    ("guardwhitespace", OutputOptions::EAN_UPC_GUARD_WHITESPACE),
+    ("init", OutputOptions::READER_INIT),
+    ("noquietzones", OutputOptions::BARCODE_NO_QUIET_ZONES),
+    ("quietzones", OutputOptions::BARCODE_QUIET_ZONES),
+    ("small", OutputOptions::SMALL_TEXT),
+];
+
+impl Options {
+    /// Parses `zint` command line arguments (e.g. `--barcode=58 --scale=2`)
+    /// into options.
+    ///
+    /// Values can be given as `--name=value` or `--name value`, and `-b`,
+    /// `-w` and `-r` short options are supported. A leading `zint` program
+    /// name is skipped. Like the CLI, input is treated as UTF-8 unless
+    /// `--binary` or `--gs1` is given.
+    ///
+    /// Arguments which don't configure the symbol (e.g. `--data`, `--output`
+    /// or `--batch`) are rejected, as is `--rows` for symbologies other than
+    /// Codablock-F, which take it in `option_3`.
+    pub fn from_cli_args<I, S>(args: I) -> Result<Options, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut args = args
+            .into_iter()
+            .map(|it| it.as_ref().to_string())
+            .peekable();
+        if args.peek().is_some_and(|it| is_program_name(it)) {
+            args.next();
+        }
+
+        let mut parsed = Vec::new();
+        while let Some(arg) = args.next() {
+            let (name, value) = split_arg(&arg)?;
+            let is_flag = FLAGS.contains(&name.as_str())
+                || INPUT_FLAGS.iter().any(|(it, _)| *it == name)
+                || OUTPUT_FLAGS.iter().any(|(it, _)| *it == name);
+            let is_valued = VALUED.contains(&name.as_str());
+            if !is_flag && !is_valued {
+                return Err(invalid(&arg, "unsupported option"));
+            }
+            let value = match (is_valued, value) {
+                (true, Some(value)) => Some(value),
+                (true, None) => Some(args.next().ok_or_else(|| invalid(&arg, "missing value"))?),
+                (false, Some(_)) => return Err(invalid(&arg, "option doesn't take a value")),
+                (false, None) => None,
+            };
+            parsed.push((arg, name, value.unwrap_or_default()));
+        }
+
+        let mut options = Options::default();
+        let mut format = InputMode::UNICODE;
+        let mut input_flags = InputMode::empty();
+        let mut output_options = OutputOptions::empty();
+        let mut option_3: Option<u32> = None;
+        let mut rows = None;
+        for (arg, name, value) in parsed {
+            let int = || {
+                value
+                    .trim()
+                    .parse::<i32>()
+                    .map_err(|_| invalid(&arg, "expected an integer"))
+            };
+            let float = || {
+                value
+                    .trim()
+                    .parse::<f32>()
+                    .map_err(|_| invalid(&arg, "expected a number"))
+            };
+            match name.as_str() {
+                "barcode" => options.symbology = Symbology::from_str(&value)?,
+                "height" => options.height = Some(float()?),
+                "scale" => options.scale = Some(float()?),
+                "scalexdimdp" => {
+                    let (x_dimension, dpmm) = parse_x_dimension_dp(&value)
+                        .ok_or_else(|| invalid(&arg, "expected X-dimension[,resolution]"))?;
+                    options.x_dimension_mm = Some(x_dimension);
+                    options.dpmm = dpmm;
+                }
+                "whitesp" => options.whitespace_width = Some(int()?),
+                "vwhitesp" => options.whitespace_height = Some(int()?),
+                "border" => options.border_width = Some(int()?),
+                "fg" | "fgcolor" | "fgcolour" => {
+                    options.fg_color = Some(Color::from_zint_string(&value)?)
+                }
+                "bg" | "bgcolor" | "bgcolour" => {
+                    options.bg_color = Some(Color::from_zint_string(&value)?)
+                }
+                "nobackground" => options.bg_color = Some(Color::TRANSPARENT),
+                "reverse" => {
+                    options.fg_color = Some(Color::Rgba {
+                        r: u8::MAX,
+                        g: u8::MAX,
+                        b: u8::MAX,
+                        a: u8::MAX,
+                    });
+                    options.bg_color = Some(Color::BLACK);
+                }
+                "primary" => options.primary = Some(value.clone()),
+                "secure" | "mode" => options.option_1 = Some(int()?),
+                "rows" => rows = Some((arg.clone(), int()?)),
+                "vers" | "cols" | "addongap" => options.option_2 = Some(int()?),
+                "scmvv" => options.option_2 = Some(int()? + 1),
+                "mask" => {
+                    let mask = int()?;
+                    if !(0..=7).contains(&mask) {
+                        return Err(invalid(&arg, "mask must be between 0 and 7"));
+                    }
+                    option_3 = Some(option_3.unwrap_or_default() | (mask as u32 + 1) << 8);
+                }
+                "fullmultibyte" => {
+                    option_3 = Some(option_3.unwrap_or_default() | ZINT_FULL_MULTIBYTE);
+                }
+                "square" => option_3 = Some(DM_SQUARE),
+                "dmre" => option_3 = Some(DM_DMRE),
+                "dmiso144" => option_3 = Some(DM_ISO_144),
+                "notext" => options.show_hrt = Some(false),
+                "binary" => format = InputMode::DATA,
+                "gs1" => format = InputMode::GS1,
+                "eci" => options.eci = Some(int()?),
+                "dotsize" => options.dot_size = Some(float()?),
+                "textgap" => options.text_gap = Some(float()?),
+                "guarddescent" => options.guard_descent = Some(float()?),
+                "rotate" => options.rotation = Some(Rotation::try_from(int()?)?),
+                "structapp" => {
+                    let mut parts = value.splitn(3, ',');
+                    let mut part = || {
+                        parts
+                            .next()
+                            .and_then(|it| it.trim().parse::<i32>().ok())
+                            .ok_or_else(|| invalid(&arg, "expected index,count[,id]"))
+                    };
+                    let structured_append = StructuredAppend::new(part()?, part()?)?;
+                    options.structured_append = Some(match parts.next() {
+                        Some(id) => structured_append.with_id(id)?,
+                        None => structured_append,
+                    });
+                }
+                flag => {
+                    if let Some((_, mode)) = INPUT_FLAGS.iter().find(|(it, _)| *it == flag) {
+                        input_flags |= *mode;
+                    } else if let Some((_, option)) =
+                        OUTPUT_FLAGS.iter().find(|(it, _)| *it == flag)
+                    {
+                        output_options |= *option;
+                    } else {
+                        return Err(invalid(&arg, "unsupported option"));
+                    }
+                }
+            }
+        }
+
+        // `--rows` sets `option_1` only for Codablock-F; other symbologies
+        // (e.g. PDF417) take it in `option_3`, which `Option3` can't represent
+        if let Some((arg, rows)) = rows {
+            match options.symbology {
+                Symbology::CodablockF | Symbology::HIBCCodablockF => options.option_1 = Some(rows),
+                _ => return Err(invalid(&arg, "rows are only supported for Codablock-F")),
+            }
+        }
+
+        options.input_mode = Some(format | input_flags);
+        if !output_options.is_empty() {
+            options.output_options = Some(output_options);
+        }
+        if let Some(option_3) = option_3 {
+            options.option_3 = Option3::new(options.symbology, option_3)?;
+        }
+        Ok(options)
+    }
+
+    /// Parses a `zint` command line (e.g. `zint --barcode=58 --scale=2`) into
+    /// options.
+    ///
+    /// The command line is split into arguments like a POSIX shell would,
+    /// honoring single and double quotes and backslash escapes. See
+    /// [`from_cli_args`](Options::from_cli_args) for accepted arguments.
+    pub fn from_cli(command_line: &str) -> Result<Options, Error> {
+        Options::from_cli_args(split_command_line(command_line)?)
+    }
+
+    /// Returns `zint` command line arguments equivalent to the options.
+    ///
+    /// Options the CLI has no flag for are left out: `dpmm` when `scale` is
+    /// set without `x_dimension_mm`, Ultracode compression and output options
+    /// other than those selecting symbol appearance (e.g.
+    /// [`BARCODE_STDOUT`](OutputOptions::BARCODE_STDOUT)).
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let symbology = self.symbology;
+        let mut args = Vec::new();
+        push(&mut args, "barcode", Some(symbology.id()));
+        push(&mut args, "height", self.height);
+        push(&mut args, "scale", self.scale);
+        let x_dimension = match self.x_dimension_mm {
+            Some(x_dimension) => Some(x_dimension),
+            None if self.scale.is_none() => self.dpmm.map(|_| symbology.default_x_dimension_mm()),
+            None => None,
+        };
+        push(
+            &mut args,
+            "scalexdimdp",
+            x_dimension.map(|x_dimension| match self.dpmm {
+                Some(dpmm) => format!("{},{}", x_dimension, dpmm),
+                None => x_dimension.to_string(),
+            }),
+        );
+        push(&mut args, "whitesp", self.whitespace_width);
+        push(&mut args, "vwhitesp", self.whitespace_height);
+        push(&mut args, "border", self.border_width);
+        push(&mut args, "fg", self.fg_color.map(cli_color));
+        push(&mut args, "bg", self.bg_color.map(cli_color));
+        push(&mut args, "primary", self.primary.as_deref());
+
+        let (mut option_1, mut option_2) = (self.option_1, self.option_2);
+        if let Some(ref symbology_options) = self.symbology_options {
+            let (typed_1, typed_2) = symbology_options.option_values();
+            option_1 = typed_1.or(option_1);
+            option_2 = typed_2.or(option_2);
+        }
+        let option_1_name = match symbology {
+            Symbology::MaxiCode => "mode",
+            _ if symbology.category() == SymbologyCategory::Composite => "mode",
+            Symbology::CodablockF | Symbology::HIBCCodablockF => "rows",
+            _ => "secure",
+        };
+        push(&mut args, option_1_name, option_1);
+        match symbology {
+            Symbology::MaxiCode => push(&mut args, "scmvv", option_2.map(|it| it - 1)),
+            Symbology::PDF417
+            | Symbology::PDF417Comp
+            | Symbology::MicroPDF417
+            | Symbology::HIBCPDF
+            | Symbology::HIBCMicPDF
+            | Symbology::DotCode
+            | Symbology::CodablockF
+            | Symbology::HIBCCodablockF
+            | Symbology::DBarExpStk
+            | Symbology::DBarExpStkCC => push(&mut args, "cols", option_2),
+            Symbology::EANX
+            | Symbology::EANXChk
+            | Symbology::EANXCC
+            | Symbology::UPCA
+            | Symbology::UPCAChk
+            | Symbology::UPCACC
+            | Symbology::UPCE
+            | Symbology::UPCEChk
+            | Symbology::UPCECC
+            | Symbology::ISBNX => push(&mut args, "addongap", option_2),
+            _ => push(&mut args, "vers", option_2),
+        }
+
+        match self.option_3 {
+            Some(Option3::DataMatrix(option)) => args.push(
+                match option {
+                    DataMatrixOption::Square => "--square",
+                    DataMatrixOption::DMRE => "--dmre",
+                    DataMatrixOption::ISO144 => "--dmiso144",
+                }
+                .to_string(),
+            ),
+            Some(Option3::QRMatrix(option)) => {
+                let mask = option.difference(QRMatrixOption::FULL_MULITIBYTE).bits() >> 8;
+                if mask > 0 {
+                    args.push(format!("--mask={}", mask - 1));
+                }
+                if option.contains(QRMatrixOption::FULL_MULITIBYTE) {
+                    args.push("--fullmultibyte".to_string());
+                }
+            }
+            Some(Option3::Ultracode(_)) | None => {}
+        }
+
+        if let Some(output_options) = self.output_options {
+            for (name, option) in OUTPUT_FLAGS {
+                if output_options.contains(*option) {
+                    args.push(format!("--{}", name));
+                }
+            }
+        }
+        if self.show_hrt == Some(false) {
+            args.push("--notext".to_string());
+        }
+
+        // Zint (and these options) default to binary data while the CLI
+        // defaults to UTF-8
+        let input_mode = self.input_mode.unwrap_or(InputMode::DATA);
+        match input_mode.format() {
+            format if format.contains(InputMode::GS1) => args.push("--gs1".to_string()),
+            format if format.contains(InputMode::UNICODE) => {}
+            _ => args.push("--binary".to_string()),
+        }
+        for (name, mode) in INPUT_FLAGS {
+            if input_mode.contains(*mode) {
+                args.push(format!("--{}", name));
+            }
+        }
+
+        push(&mut args, "eci", self.eci);
+        push(&mut args, "dotsize", self.dot_size);
+        push(&mut args, "textgap", self.text_gap);
+        push(&mut args, "guarddescent", self.guard_descent);
+        push(&mut args, "rotate", self.rotation.map(|it| it.degrees()));
+        push(
+            &mut args,
+            "structapp",
+            self.structured_append.as_ref().map(|it| match it.id() {
+                Some(id) => format!("{},{},{}", it.index(), it.count(), id),
+                None => format!("{},{}", it.index(), it.count()),
+            }),
+        );
+
+        args
+    }
+}
+
+/// Appends `--name=value` argument if `value` is set.
+fn push(args: &mut Vec<String>, name: &str, value: Option<impl Display>) {
+    if let Some(value) = value {
+        args.push(format!("--{}={}", name, value));
+    }
+}
+
+fn invalid(arg: &str, reason: &'static str) -> Error {
+    Error::InvalidCliArgument {
+        arg: arg.to_string(),
+        reason,
+    }
+}
+
+fn is_program_name(arg: &str) -> bool {
+    let name = arg.rsplit(['/', '\\']).next().unwrap_or(arg);
+    name == "zint" || name.eq_ignore_ascii_case("zint.exe")
+}
+
+/// Splits `command_line` into arguments on unquoted whitespace.
+fn split_command_line(command_line: &str) -> Result<Vec<String>, Error> {
+    let mut result = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = command_line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => result.extend(current.take()),
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err(invalid(command_line, "unterminated quote")),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => arg.push(c),
+                            Some(c) => {
+                                arg.push('\\');
+                                arg.push(c);
+                            }
+                            None => return Err(invalid(command_line, "unterminated quote")),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err(invalid(command_line, "unterminated quote")),
+                    }
+                }
+            }
+            '\\' => {
+                let arg = current.get_or_insert_with(String::new);
+                if let Some(c) = chars.next() {
+                    arg.push(c);
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    result.extend(current);
+    Ok(result)
+}
+
+/// Splits argument into long option name and inline value.
+fn split_arg(arg: &str) -> Result<(String, Option<String>), Error> {
+    if let Some(long) = arg.strip_prefix("--") {
+        return Ok(match long.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (long.to_string(), None),
+        });
+    }
+    let Some(short) = arg.strip_prefix('-') else {
+        return Err(invalid(arg, "expected an option"));
+    };
+    let mut chars = short.chars();
+    let name = match chars.next() {
+        Some('b') => "barcode",
+        Some('w') => "whitesp",
+        Some('r') => "reverse",
+        _ => return Err(invalid(arg, "unsupported option")),
+    };
+    let value = chars.as_str();
+    let value = value.strip_prefix('=').unwrap_or(value);
+    Ok((
+        name.to_string(),
+        (!value.is_empty()).then(|| value.to_string()),
+    ))
+}
+
+/// Parses `--scalexdimdp` value `X[,R]`, where X-dimension is in millimeters
+/// (or inches with `in` suffix) and resolution in dots per millimeter (or
+/// dots per inch with `dpi` suffix).
+fn parse_x_dimension_dp(value: &str) -> Option<(f32, Option<f32>)> {
+    let (x_dimension, resolution) = match value.split_once(',') {
+        Some((x_dimension, resolution)) => (x_dimension, Some(resolution)),
+        None => (value, None),
+    };
+    let x_dimension = x_dimension.trim();
+    let x_dimension = match x_dimension.strip_suffix("in") {
+        Some(inches) => inches.trim().parse::<f32>().ok()? * MM_PER_INCH,
+        None => x_dimension.trim_end_matches("mm").trim().parse().ok()?,
+    };
+    let dpmm = match resolution.map(str::trim) {
+        Some(resolution) => Some(match resolution.strip_suffix("dpi") {
+            Some(dpi) => dpi.trim().parse::<f32>().ok()? / MM_PER_INCH,
+            None => resolution.trim_end_matches("dpmm").trim().parse().ok()?,
+        }),
+        None => None,
+    };
+    Some((x_dimension, dpmm))
+}
+
+/// Formats color as accepted by `--fg` and `--bg`.
+fn cli_color(color: Color) -> String {
+    match color {
+        Color::Rgba { .. } => color.to_string(),
+        Color::Cmyk { .. } => color.to_zint_string(),
+    }
+}
@@ -1,37 +1,101 @@
 use std::str::FromStr;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::error::Error;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Color {
-    pub r: u8,
-    pub g: u8,
-    pub b: u8,
-    pub a: u8,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// RGB color with an alpha channel
+    Rgba { r: u8, g: u8, b: u8, a: u8 },
+    /// CMYK color with components expressed in percent (0-100)
+    ///
+    /// Passed to Zint as is, so it's preserved exactly in Encapsulated
+    /// PostScript and TIF output with
+    /// [`CMYK_COLOR`](super::output_options::OutputOptions::CMYK_COLOR) set.
+    /// Other formats convert it to RGB.
+    Cmyk { c: u8, m: u8, y: u8, k: u8 },
 }
 
 impl Color {
-    pub const BLACK: Color = Color {
+    pub const BLACK: Color = Color::Rgba {
         r: 0,
         g: 0,
         b: 0,
         a: u8::MAX,
     };
-    pub const TRANSPARENT: Color = Color {
+    pub const TRANSPARENT: Color = Color::Rgba {
         r: u8::MAX,
         g: u8::MAX,
         b: u8::MAX,
         a: 0,
     };
 
+    /// Returns color in format accepted by Zint `fgcolour`/`bgcolour` fields:
+    /// `RRGGBBAA` hex for RGB colors and decimal `C,M,Y,K` for CMYK colors.
+    pub fn to_zint_string(&self) -> String {
+        match *self {
+            Color::Rgba { r, g, b, a } => hex::encode([r, g, b, a]),
+            Color::Cmyk { c, m, y, k } => format!("{},{},{},{}", c, m, y, k),
+        }
+    }
+
+    /// Returns color as `RRGGBBAA` hex for RGB colors.
+    ///
+    /// CMYK colors aren't converted to RGB: they're returned as decimal
+    /// `C,M,Y,K` instead, which isn't a hex string. Use
+    /// [`to_zint_string`](Color::to_zint_string), or match on
+    /// [`Color::Rgba`] if hex is required.
+    #[deprecated(note = "use `to_zint_string`, which also handles CMYK colors")]
     pub fn to_hex_string(&self) -> String {
-        hex::encode([self.r, self.g, self.b, self.a])
+        self.to_zint_string()
+    }
+
+    /// Parses color in format accepted by Zint `fgcolour`/`bgcolour` fields
+    /// and `--fg`/`--bg` command line options, which also includes bare
+    /// `C,M,Y,K` CMYK colors.
+    pub fn from_zint_string(s: &str) -> Result<Self, Error> {
+        if s.contains(',') {
+            Color::cmyk_from_str(s)
+        } else {
+            Color::from_str(s)
+        }
     }
 
     pub fn is_opaque(&self) -> bool {
-        self.a == u8::MAX
+        match *self {
+            Color::Rgba { a, .. } => a == u8::MAX,
+            Color::Cmyk { .. } => true,
+        }
+    }
+
+    /// Parses comma separated CMYK components, optionally with `%` suffix.
+    fn cmyk_from_str(components: &str) -> Result<Self, Error> {
+        let components = components
+            .split(',')
+            .map(|it| {
+                let it = it.trim();
+                it.strip_suffix('%').unwrap_or(it).trim().parse::<u8>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::InvalidColor {
+                reason: "CMYK components must be integers",
+            })?;
+        Color::cmyk_from_components(&components)
+    }
+
+    fn cmyk_from_components(components: &[u8]) -> Result<Self, Error> {
+        if components.iter().any(|it| *it > 100) {
+            return Err(Error::InvalidColor {
+                reason: "CMYK components must be in 0-100 range",
+            });
+        }
+        match *components {
+            [c, m, y, k] => Ok(Color::Cmyk { c, m, y, k }),
+            _ => Err(Error::InvalidColor {
+                reason: "CMYK color requires exactly 4 components",
+            }),
+        }
     }
 }
 
@@ -39,6 +103,14 @@ impl FromStr for Color {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let cmyk = lower
+            .strip_prefix("cmyk(")
+            .and_then(|it| it.strip_suffix(')'));
+        if let Some(components) = cmyk {
+            return Color::cmyk_from_str(components);
+        }
+
         let v = s.strip_prefix('#').unwrap_or(s);
         let v: Vec<u8> = if v.len() == 3 || v.len() == 4 {
             v.chars().flat_map(|it| [it as u8, it as u8]).collect()
@@ -48,7 +120,7 @@ impl FromStr for Color {
 
         let bytes = hex::decode(v.as_slice()).map_err(Error::InvalidColorEncoding)?;
 
-        Ok(Color {
+        Ok(Color::Rgba {
             r: *bytes.first().ok_or(Error::InvalidColor {
                 reason: "hex too short",
             })?,
@@ -63,6 +135,32 @@ impl FromStr for Color {
     }
 }
 
+impl std::fmt::Display for Color {
+    /// Formats RGB colors as `RRGGBB` (or `RRGGBBAA` if not opaque) hex and
+    /// CMYK colors as `cmyk(C,M,Y,K)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Color::Rgba {
+                r,
+                g,
+                b,
+                a: u8::MAX,
+            } => write!(f, "{}", hex::encode([r, g, b])),
+            Color::Rgba { r, g, b, a } => write!(f, "{}", hex::encode([r, g, b, a])),
+            Color::Cmyk { c, m, y, k } => write!(f, "cmyk({},{},{},{})", c, m, y, k),
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 impl<'de> Deserialize<'de> for Color {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -81,6 +179,14 @@ impl<'de> Deserialize<'de> for Color {
             B,
             Alpha,
             A,
+            Cyan,
+            C,
+            Magenta,
+            M,
+            Yellow,
+            Y,
+            Black,
+            K,
         }
 
         struct ColorVisitor;
@@ -88,7 +194,7 @@ impl<'de> Deserialize<'de> for Color {
             type Value = Color;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("RGBA color")
+                formatter.write_str("RGBA or CMYK color")
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -120,7 +226,7 @@ impl<'de> Deserialize<'de> for Color {
                 let b = seq.next_element()?.ok_or(de::Error::missing_field("b"))?;
                 let a = seq.next_element()?.unwrap_or(u8::MAX);
 
-                Ok(Color { r, g, b, a })
+                Ok(Color::Rgba { r, g, b, a })
             }
 
             fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
@@ -131,6 +237,7 @@ impl<'de> Deserialize<'de> for Color {
                 let mut g = None;
                 let mut b = None;
                 let mut a = None;
+                let mut cmyk: [Option<u8>; 4] = [None; 4];
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -158,10 +265,37 @@ impl<'de> Deserialize<'de> for Color {
                             }
                             a = Some(map.next_value()?);
                         }
+                        cmyk_field => {
+                            let (i, name) = match cmyk_field {
+                                Fields::Cyan | Fields::C => (0, "c"),
+                                Fields::Magenta | Fields::M => (1, "m"),
+                                Fields::Yellow | Fields::Y => (2, "y"),
+                                _ => (3, "k"),
+                            };
+                            if cmyk[i].is_some() {
+                                return Err(de::Error::duplicate_field(name));
+                            }
+                            cmyk[i] = Some(map.next_value()?);
+                        }
+                    }
+                }
+
+                if cmyk.iter().any(Option::is_some) {
+                    if r.is_some() || g.is_some() || b.is_some() || a.is_some() {
+                        return Err(de::Error::custom(Error::InvalidColor {
+                            reason: "mixed RGBA and CMYK components",
+                        }));
                     }
+                    let components = [
+                        cmyk[0].ok_or_else(|| de::Error::missing_field("c"))?,
+                        cmyk[1].ok_or_else(|| de::Error::missing_field("m"))?,
+                        cmyk[2].ok_or_else(|| de::Error::missing_field("y"))?,
+                        cmyk[3].ok_or_else(|| de::Error::missing_field("k"))?,
+                    ];
+                    return Color::cmyk_from_components(&components).map_err(de::Error::custom);
                 }
 
-                Ok(Color {
+                Ok(Color::Rgba {
                     r: r.ok_or_else(|| de::Error::missing_field("r"))?,
                     g: g.ok_or_else(|| de::Error::missing_field("g"))?,
                     b: b.ok_or_else(|| de::Error::missing_field("b"))?,
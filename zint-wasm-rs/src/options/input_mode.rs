@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, ValidationFailiure};
 
@@ -41,42 +41,88 @@ impl InputMode {
 
         None
     }
+
+    /// Returns the data format (`DATA`, `UNICODE` or `GS1`).
+    pub fn format(&self) -> InputMode {
+        if self.contains(Self::GS1) {
+            Self::GS1
+        } else if self.contains(Self::UNICODE) {
+            Self::UNICODE
+        } else {
+            Self::DATA
+        }
+    }
 }
 
-impl<'de> Deserialize<'de> for InputMode {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+macro_rules! decl_names {
+    ($($opt: ident: [$name: literal $(, $alias: literal)*],)+) => {
+        /// Modes along with their names used for serialization
+        const NAMED: &[(InputMode, &str)] = &[$((InputMode::$opt, $name)),+];
+
+        fn opt_for_name(name: &str) -> Option<InputMode> {
+            let lower = name.to_lowercase().replace('_', "-");
+            let clear = lower.strip_suffix("-mode").unwrap_or(&lower);
+            $(
+            if [$name $(, $alias)*].contains(&clear) {
+                return Some(InputMode::$opt)
+            }
+            )+
+            None
+        }
+    };
+}
+
+decl_names![
+    DATA: ["data"],
+    UNICODE: ["unicode"],
+    GS1: ["gs1"],
+
+    ESCAPE: ["escape"],
+    GS1_PARENTHESES: ["gs1-parentheses", "gs1paren"],
+    GS1_NO_CHECK: ["gs1-no-check", "gs1nocheck"],
+    HEIGHT_PER_ROW: ["height-per-row", "heightperrow"],
+    FAST: ["fast"],
+    EXTRA_ESCAPE: ["extra-escape"],
+];
+
+impl Serialize for InputMode {
+    /// Serializes input mode as a list of names starting with the data format,
+    /// or as a number if some options don't have a name.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
-        D: serde::Deserializer<'de>,
+        S: serde::Serializer,
     {
-        use serde::de;
+        use serde::ser::SerializeSeq;
 
-        macro_rules! decl_names {
-            ($($opt: ident: [$($value: literal),+],)+) => {
-                fn opt_for_name(name: &str) -> Option<InputMode> {
-                    let lower = name.to_lowercase().replace('_', "-");
-                    let clear = lower.strip_suffix("-mode").unwrap_or(&lower);
-                    $(
-                    if [$($value),+].contains(&clear) {
-                        return Some(InputMode::$opt)
-                    }
-                    )+
-                    None
-                }
-            };
+        let named = NAMED
+            .iter()
+            .fold(InputMode::empty(), |acc, (opt, _)| acc.union(*opt));
+        if !named.contains(*self) || self.validate().is_some() {
+            return serializer.serialize_u32(self.bits());
         }
 
-        decl_names![
-            DATA: ["data"],
-            UNICODE: ["unicode"],
-            GS1: ["gs1"],
+        let format = self.format();
+        let names = NAMED.iter().filter(|(opt, _)| {
+            if opt.is_empty() || opt.intersects(InputMode::UNICODE | InputMode::GS1) {
+                opt.bits() == format.bits()
+            } else {
+                self.contains(*opt)
+            }
+        });
+        let mut seq = serializer.serialize_seq(Some(names.clone().count()))?;
+        for (_, name) in names {
+            seq.serialize_element(name)?;
+        }
+        seq.end()
+    }
+}
 
-            ESCAPE: ["escape"],
-            GS1_PARENTHESES: ["gs1-parentheses", "gs1paren"],
-            GS1_NO_CHECK: ["gs1-no-check", "gs1nocheck"],
-            HEIGHT_PER_ROW: ["height-per-row", "heightperrow"],
-            FAST: ["fast"],
-            EXTRA_ESCAPE: ["extra-escape"],
-        ];
+impl<'de> Deserialize<'de> for InputMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de;
 
         struct InputModeVisitor;
         impl<'de> de::Visitor<'de> for InputModeVisitor {
@@ -133,14 +179,10 @@ impl<'de> Deserialize<'de> for InputMode {
                 A: de::SeqAccess<'de>,
             {
                 let mut result = InputMode::empty();
-                while let Some(el) = seq.next_element::<&str>()? {
-                    result = match opt_for_name(el) {
+                while let Some(el) = seq.next_element::<String>()? {
+                    result = match opt_for_name(&el) {
                         Some(it) => result.union(it),
-                        None => {
-                            return Err(de::Error::custom(Error::UnknownInputOption(
-                                el.to_string(),
-                            )))
-                        }
+                        None => return Err(de::Error::custom(Error::UnknownInputOption(el))),
                     }
                 }
 
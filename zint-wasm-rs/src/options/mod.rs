@@ -1,67 +1,123 @@
-use serde::Deserialize;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
 
 use self::{
-    color::Color, input_mode::InputMode, option3::Option3, output_options::OutputOptions,
+    color::Color,
+    input_mode::InputMode,
+    option3::{Option3, RawOption3},
+    output_options::OutputOptions,
+    rotation::Rotation,
+    structured_append::StructuredAppend,
     symbology::Symbology,
+    symbology_options::{RawSymbologyOptions, SymbologyOptions},
 };
 
+pub mod builder;
 pub mod capability;
+mod cli;
 pub mod color;
 pub mod input_mode;
 pub mod option3;
 pub mod output_options;
+pub mod rotation;
+pub mod structured_append;
 pub mod symbology;
+pub mod symbology_options;
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "typst", serde(rename_all = "kebab-case"))]
-#[serde(default)]
+// derived implementations are used by `Serialize` and `Deserialize` impls
+// below which handle `option_3` and `symbology_options` for the symbology
+#[serde(default, remote = "Self")]
 pub struct Options {
     /// Barcode symbol to use
-    #[serde(flatten)]
     pub symbology: Symbology,
     /// Barcode height in X-dimensions (ignored for fixed-width barcodes)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<f32>,
     /// Scale factor when printing barcode, i.e. adjusts X-dimension. Default 1
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scale: Option<f32>,
+    /// Width of the narrowest module in millimeters; overrides `scale`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_dimension_mm: Option<f32>,
+    /// Resolution of output in dots per millimeter. Sets `scale` from default
+    /// X-dimension of the symbology unless `scale` or `x_dimension_mm` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dpmm: Option<f32>,
     /// Width in X-dimensions of whitespace to left & right of barcode
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub whitespace_width: Option<i32>,
     /// Height in X-dimensions of whitespace above & below the barcode
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub whitespace_height: Option<i32>,
     /// Size of border in X-dimensions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub border_width: Option<i32>,
     /// Various output parameters (bind, box etc, see below)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub output_options: Option<OutputOptions>,
     /// foreground color
-    #[serde(alias = "fg_colour")]
+    #[serde(alias = "fg_colour", skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "typst", serde(alias = "stroke"))]
     pub fg_color: Option<Color>,
     /// background color
-    #[serde(alias = "bg_colour")]
+    #[serde(alias = "bg_colour", skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "typst", serde(alias = "fill"))]
     pub bg_color: Option<Color>,
     /// Primary message data (MaxiCode, Composite)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub primary: Option<String>,
     /// Symbol-specific options
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub option_1: Option<i32>,
     /// Symbol-specific options
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub option_2: Option<i32>,
-    /// Symbol-specific options
+    /// Symbol-specific options, checked against `symbology`
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
     pub option_3: Option<Option3>,
+    /// Typed symbology specific options; override `option_1` and `option_2`
+    ///
+    /// (De)serialized as readable fields (e.g. `ecc: "H"` or `columns: 8`)
+    /// interpreted for `symbology`.
+    #[serde(skip)]
+    pub symbology_options: Option<SymbologyOptions>,
     /// Show (1) or hide (0) Human Readable Text (HRT). Default 1
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub show_hrt: Option<bool>,
     /// Encoding of input data
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub input_mode: Option<InputMode>,
     /// Extended Channel Interpretation.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub eci: Option<i32>,
     /// Size of dots used in BARCODE_DOTTY_MODE.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dot_size: Option<f32>,
     /// Gap between barcode and text (HRT) in X-dimensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text_gap: Option<f32>,
     /// Height in X-dimensions that EAN/UPC guard bars descend.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub guard_descent: Option<f32>,
+    /// Clockwise rotation of the output (0, 90, 180 or 270 degrees).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<Rotation>,
+    /// Structured Append info.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured_append: Option<StructuredAppend>,
 }
 
 impl Options {
+    /// Range of `x_dimension_mm` supported by Zint, excluding `0`
+    pub const X_DIMENSION_MM_RANGE: RangeInclusive<f32> = 0.0..=10.0;
+    /// Range of `dpmm` supported by Zint
+    pub const DPMM_RANGE: RangeInclusive<f32> = 0.0..=1000.0;
+
     pub fn with_symbology(symbology: Symbology) -> Self {
         Self {
             symbology,
@@ -69,3 +125,89 @@ impl Options {
         }
     }
 }
+
+/// Returns an error if X-dimension or resolution is out of range supported
+/// by Zint.
+pub(crate) fn check_scaling(
+    symbology: Symbology,
+    x_dimension_mm: Option<f32>,
+    dpmm: Option<f32>,
+) -> Result<(), Error> {
+    let out_of_range = |which, value, range| Error::OutOfRange {
+        which,
+        value,
+        range,
+        symbology,
+    };
+    if let Some(x_dimension_mm) = x_dimension_mm {
+        if x_dimension_mm == 0.0 || !Options::X_DIMENSION_MM_RANGE.contains(&x_dimension_mm) {
+            return Err(out_of_range(
+                "x_dimension_mm",
+                x_dimension_mm,
+                Options::X_DIMENSION_MM_RANGE,
+            ));
+        }
+    }
+    if let Some(dpmm) = dpmm {
+        if !Options::DPMM_RANGE.contains(&dpmm) {
+            return Err(out_of_range("dpmm", dpmm, Options::DPMM_RANGE));
+        }
+    }
+    Ok(())
+}
+
+impl Serialize for Options {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(Serialize)]
+        struct WithSymbologyOptions<'a> {
+            #[serde(flatten, serialize_with = "Options::serialize")]
+            options: &'a Options,
+            #[serde(flatten)]
+            symbology_options: &'a Option<SymbologyOptions>,
+        }
+
+        WithSymbologyOptions {
+            options: self,
+            symbology_options: &self.symbology_options,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Options {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct WithSymbologyOptions {
+            #[serde(flatten, deserialize_with = "Options::deserialize")]
+            options: Options,
+            #[serde(default)]
+            #[cfg_attr(feature = "typst", serde(rename = "option-3"))]
+            option_3: Option<RawOption3>,
+            #[serde(flatten)]
+            symbology_options: RawSymbologyOptions,
+        }
+
+        let WithSymbologyOptions {
+            mut options,
+            option_3,
+            symbology_options,
+        } = WithSymbologyOptions::deserialize(deserializer)?;
+        if let Some(option_3) = option_3 {
+            options.option_3 = option_3
+                .resolve(options.symbology)
+                .map_err(serde::de::Error::custom)?;
+        }
+        options.symbology_options = symbology_options
+            .resolve(options.symbology)
+            .map_err(serde::de::Error::custom)?;
+        check_scaling(options.symbology, options.x_dimension_mm, options.dpmm)
+            .map_err(serde::de::Error::custom)?;
+        Ok(options)
+    }
+}
@@ -1,12 +1,12 @@
-use std::fmt::Debug;
-
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use zint_wasm_sys::*;
 
 use crate::error::Error;
 
+use super::{capability::CapabilityFlags, symbology::Symbology};
+
 /// Data Matrix specific options
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 #[allow(clippy::upper_case_acronyms)]
 #[serde(untagged, try_from = "u32")]
 #[repr(u32)]
@@ -96,7 +96,7 @@ pub enum QRMask {
 
 bitflags::bitflags! {
     /// QR, Han Xin, Grid Matrix specific options
-    #[derive(Debug, Clone, Copy, Deserialize)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
     #[serde(transparent)]
     pub struct QRMatrixOption: u32 {
         /// Increase non-ASCII data density
@@ -141,7 +141,7 @@ impl TryFrom<u32> for QRMatrixOption {
 }
 
 /// Ultracode specific option
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 #[serde(untagged, try_from = "u32")]
 #[repr(u32)]
 pub enum UltracodeOption {
@@ -165,92 +165,134 @@ impl TryFrom<u32> for UltracodeOption {
     }
 }
 
-/// Option3 is an `u32` whose variant is determined by
-/// [`Options::symbology`](super::Options::symbology) value.
-#[derive(Clone, Copy)]
-#[repr(C)]
-pub union Option3 {
-    pub data_matrix: DataMatrixOption,
-    pub qr_matrix: QRMatrixOption,
-    pub ultracode: UltracodeOption,
+/// Symbology specific `option_3` value.
+///
+/// Meaning of `option_3` depends on [`Options::symbology`](super::Options::symbology)
+/// so the value is only valid for symbologies accepted by [`check`](Option3::check).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Option3 {
+    /// Data Matrix (and HIBC Data Matrix) size selection
+    DataMatrix(DataMatrixOption),
+    /// QR Code, Micro QR, rMQR, UPNQR, Han Xin and Grid Matrix options
+    QRMatrix(QRMatrixOption),
+    /// Ultracode options
+    Ultracode(UltracodeOption),
 }
 
 impl Option3 {
-    pub fn as_i32(&self) -> i32 {
-        let result: u32 = unsafe {
-            // Safety: All variants are u32
-            std::mem::transmute(*self)
+    /// Interprets raw `option_3` `value` for `symbology`.
+    ///
+    /// Returns `None` for `0`, which Zint treats as no option for all
+    /// symbologies.
+    pub fn new(symbology: Symbology, value: u32) -> Result<Option<Self>, Error> {
+        if value == 0 {
+            return Ok(None);
+        }
+        let result = match symbology {
+            Symbology::DataMatrix | Symbology::HIBCDM => {
+                Option3::DataMatrix(DataMatrixOption::try_from(value)?)
+            }
+            Symbology::Ultra => Option3::Ultracode(UltracodeOption::try_from(value)?),
+            _ => Option3::QRMatrix(QRMatrixOption::try_from(value)?),
         };
-
-        result as i32
+        result.check(symbology)?;
+        Ok(Some(result))
     }
-    /// # Safety
-    /// Option3 can be treated as [`DataMatrixOption`] only when
-    /// [`symbology`](super::Options::symbology) stored in parent
-    /// [`Options`](super::Options) permits so.
-    pub unsafe fn as_data_matrix(&self) -> DataMatrixOption {
-        self.data_matrix
-    }
-    /// # Safety
-    /// Option3 can be treated as [`QRMatrixOption`] only when
-    /// [`symbology`](super::Options::symbology) stored in parent
-    /// [`Options`](super::Options) permits so.
-    pub unsafe fn as_qr_matrix(&self) -> QRMatrixOption {
-        self.qr_matrix
+
+    /// Returns an error if the option can't be used with `symbology`.
+    ///
+    /// Empty [`QRMatrixOption`] is `0` and accepted by all symbologies.
+    pub fn check(&self, symbology: Symbology) -> Result<(), Error> {
+        let compatible = match self {
+            Option3::DataMatrix(_) => {
+                matches!(symbology, Symbology::DataMatrix | Symbology::HIBCDM)
+            }
+            Option3::Ultracode(_) => symbology == Symbology::Ultra,
+            Option3::QRMatrix(option) => {
+                let capabilities = symbology.capabilities();
+                let uses_mask = !option
+                    .difference(QRMatrixOption::FULL_MULITIBYTE)
+                    .is_empty();
+                let uses_full_multibyte = option.contains(QRMatrixOption::FULL_MULITIBYTE);
+                let mask = capabilities.supports(CapabilityFlags::Mask);
+                let full_multibyte = capabilities.supports(CapabilityFlags::FullMultibyte);
+                (!uses_mask || mask) && (!uses_full_multibyte || full_multibyte)
+            }
+        };
+        if compatible {
+            Ok(())
+        } else {
+            Err(Error::IncompatibleOption {
+                which: "option_3",
+                symbology,
+            })
+        }
     }
-    /// # Safety
-    /// Option3 can be treated as [`UltracodeOption`] only when
-    /// [`symbology`](super::Options::symbology) stored in parent
-    /// [`Options`](super::Options) permits so.
-    pub unsafe fn as_ultracode(&self) -> UltracodeOption {
-        self.ultracode
+
+    pub fn as_i32(&self) -> i32 {
+        let result = match self {
+            Option3::DataMatrix(option) => *option as u32,
+            Option3::QRMatrix(option) => option.bits(),
+            Option3::Ultracode(option) => *option as u32,
+        };
+        result as i32
     }
 }
 
-impl Debug for Option3 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.as_i32().fmt(f)
+impl Serialize for Option3 {
+    /// Serializes option as its name if it has one, or as a number otherwise.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = match self {
+            Option3::DataMatrix(DataMatrixOption::Square) => "square",
+            Option3::DataMatrix(DataMatrixOption::DMRE) => "dmre",
+            Option3::DataMatrix(DataMatrixOption::ISO144) => "iso-144",
+            Option3::QRMatrix(option) if *option == QRMatrixOption::FULL_MULITIBYTE => {
+                "full-multibyte"
+            }
+            Option3::QRMatrix(option) => return serializer.serialize_u32(option.bits()),
+            Option3::Ultracode(UltracodeOption::Compression) => "compression",
+        };
+        serializer.serialize_str(name)
     }
 }
 
 impl From<DataMatrixOption> for Option3 {
     fn from(value: DataMatrixOption) -> Self {
-        unsafe {
-            // Safety: DataMatrixOption is a valid Option3 variant
-            std::mem::transmute(value)
-        }
+        Option3::DataMatrix(value)
     }
 }
 impl From<QRMatrixOption> for Option3 {
     fn from(value: QRMatrixOption) -> Self {
-        unsafe {
-            // Safety: QRMatrixOption is a valid Option3 variant
-            std::mem::transmute(value)
-        }
+        Option3::QRMatrix(value)
     }
 }
 impl From<UltracodeOption> for Option3 {
     fn from(value: UltracodeOption) -> Self {
-        unsafe {
-            // Safety: UltracodeOption is a valid Option3 variant
-            std::mem::transmute(value)
-        }
+        Option3::Ultracode(value)
     }
 }
 
-impl TryFrom<u32> for Option3 {
-    type Error = Error;
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
-        // don't care which variant it is, we're just checking that the value
-        // can be stored as one
-        DataMatrixOption::try_from(value)
-            .map(From::<DataMatrixOption>::from)
-            .or_else(|_| QRMatrixOption::try_from(value).map(From::<QRMatrixOption>::from))
-            .or_else(|_| UltracodeOption::try_from(value).map(From::<UltracodeOption>::from))
+/// `option_3` value as written in options, before it's resolved for a
+/// symbology.
+#[derive(Debug, Clone)]
+pub(crate) enum RawOption3 {
+    Value(u32),
+    Named(Option3),
+}
+
+impl RawOption3 {
+    pub(crate) fn resolve(self, symbology: Symbology) -> Result<Option<Option3>, Error> {
+        match self {
+            RawOption3::Value(value) => Option3::new(symbology, value),
+            RawOption3::Named(option) => option.check(symbology).map(|_| Some(option)),
+        }
     }
 }
 
-impl<'de> Deserialize<'de> for Option3 {
+impl<'de> Deserialize<'de> for RawOption3 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -259,7 +301,7 @@ impl<'de> Deserialize<'de> for Option3 {
 
         struct Option3Visitor;
         impl<'de> de::Visitor<'de> for Option3Visitor {
-            type Value = Option3;
+            type Value = RawOption3;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
                 formatter.write_str("option_3 value")
@@ -269,14 +311,18 @@ impl<'de> Deserialize<'de> for Option3 {
             where
                 E: de::Error,
             {
-                Option3::try_from(v as u32).map_err(de::Error::custom)
+                u32::try_from(v)
+                    .map(RawOption3::Value)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Unsigned(v), &self))
             }
 
             fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                Option3::try_from(v as u32).map_err(de::Error::custom)
+                u32::try_from(v)
+                    .map(RawOption3::Value)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(v), &self))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -284,7 +330,7 @@ impl<'de> Deserialize<'de> for Option3 {
                 E: de::Error,
             {
                 let lower = v.to_lowercase().replace('_', "-");
-                Ok(match lower.as_str() {
+                Ok(RawOption3::Named(match lower.as_str() {
                     "dm-square" | "square" => Option3::from(DataMatrixOption::Square),
                     "dm-dmre" | "dmre" | "rect" => Option3::from(DataMatrixOption::DMRE),
                     "dm-iso-144" | "iso-144" => Option3::from(DataMatrixOption::ISO144),
@@ -295,7 +341,7 @@ impl<'de> Deserialize<'de> for Option3 {
                         Option3::from(UltracodeOption::Compression)
                     }
                     _ => return Err(de::Error::invalid_value(de::Unexpected::Str(v), &self)),
-                })
+                }))
             }
         }
 
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, ValidationFailiure};
 
@@ -48,6 +48,67 @@ impl OutputOptions {
     }
 }
 
+macro_rules! decl_names {
+    ($($opt: ident: [$name: literal $(, $alias: literal)*],)+) => {
+        /// Options along with their names used for serialization
+        const NAMED: &[(OutputOptions, &str)] = &[$((OutputOptions::$opt, $name)),+];
+
+        fn opt_for_name(name: &str) -> Option<OutputOptions> {
+            let lower = name.to_lowercase().replace('_', "-");
+            $(
+            if [$name $(, $alias)*].contains(&lower.as_str()) {
+                return Some(OutputOptions::$opt)
+            }
+            )+
+            None
+        }
+    };
+}
+
+decl_names![
+    BARCODE_BIND_TOP: ["barcode-bind-top"],
+    BARCODE_BIND: ["barcode-bind"],
+    BARCODE_BOX: ["barcode-box"],
+    BARCODE_STDOUT: ["barcode-stdout"],
+    READER_INIT: ["reader-init"],
+    SMALL_TEXT: ["small-text"],
+    BOLD_TEXT: ["bold-text"],
+    CMYK_COLOR: ["cmyk-color", "cmyk-colour"],
+    BARCODE_DOTTY_MODE: ["barcode-dotty-mode"],
+    GS1_GS_SEPARATOR: ["gs1-gs-separator"],
+    OUT_BUFFER_INTERMEDIATE: ["out-buffer-intermediate"],
+    BARCODE_QUIET_ZONES: ["barcode-quiet-zones"],
+    BARCODE_NO_QUIET_ZONES: ["barcode-no-quiet-zones"],
+    COMPLIANT_HEIGHT: ["compliant-height"],
+    EAN_UPC_GUARD_WHITESPACE: ["ean-upc-guard-whitespace", "eanupc-guard-whitespace"],
+    EMBED_VECTOR_FONT: ["embed-vector-font"],
+];
+
+impl Serialize for OutputOptions {
+    /// Serializes options as a list of names, or as a number if some options
+    /// don't have a name.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let named = NAMED
+            .iter()
+            .fold(OutputOptions::empty(), |acc, (opt, _)| acc.union(*opt));
+        if !named.contains(*self) {
+            return serializer.serialize_u32(self.bits());
+        }
+
+        let names = NAMED.iter().filter(|(opt, _)| self.contains(*opt));
+        let mut seq = serializer.serialize_seq(Some(names.clone().count()))?;
+        for (_, name) in names {
+            seq.serialize_element(name)?;
+        }
+        seq.end()
+    }
+}
+
 impl<'de> Deserialize<'de> for OutputOptions {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -55,39 +116,6 @@ impl<'de> Deserialize<'de> for OutputOptions {
     {
         use serde::de;
 
-        macro_rules! decl_names {
-            ($($opt: ident: [$($value: literal),+],)+) => {
-                fn opt_for_name(name: &str) -> Option<OutputOptions> {
-                    let lower = name.to_lowercase().replace('_', "-");
-                    $(
-                    if [$($value),+].contains(&lower.as_str()) {
-                        return Some(OutputOptions::$opt)
-                    }
-                    )+
-                    None
-                }
-            };
-        }
-
-        decl_names![
-            BARCODE_BIND_TOP: ["barcode-bind-top"],
-            BARCODE_BIND: ["barcode-bind"],
-            BARCODE_BOX: ["barcode-box"],
-            BARCODE_STDOUT: ["barcode-stdout"],
-            READER_INIT: ["reader-init"],
-            SMALL_TEXT: ["small-text"],
-            BOLD_TEXT: ["bold-text"],
-            CMYK_COLOR: ["cmyk-color", "cmyk-colour"],
-            BARCODE_DOTTY_MODE: ["barcode-dotty-mode"],
-            GS1_GS_SEPARATOR: ["gs1-gs-separator"],
-            OUT_BUFFER_INTERMEDIATE: ["out-buffer-intermediate"],
-            BARCODE_QUIET_ZONES: ["barcode-quiet-zones"],
-            BARCODE_NO_QUIET_ZONES: ["barcode-no-quiet-zones"],
-            COMPLIANT_HEIGHT: ["compliant-height"],
-            EAN_UPC_GUARD_WHITESPACE: ["ean-upc-guard-whitespace", "eanupc-guard-whitespace"],
-            EMBED_VECTOR_FONT: ["embed-vector-font"],
-        ];
-
         struct OutputOptionsVisitor;
         impl<'de> de::Visitor<'de> for OutputOptionsVisitor {
             type Value = OutputOptions;
@@ -129,14 +157,10 @@ impl<'de> Deserialize<'de> for OutputOptions {
                 A: de::SeqAccess<'de>,
             {
                 let mut result = OutputOptions::empty();
-                while let Some(el) = seq.next_element::<&str>()? {
-                    result = match opt_for_name(el) {
+                while let Some(el) = seq.next_element::<String>()? {
+                    result = match opt_for_name(&el) {
                         Some(it) => result.union(it),
-                        None => {
-                            return Err(de::Error::custom(Error::UnknownOutputOption(
-                                el.to_string(),
-                            )))
-                        }
+                        None => return Err(de::Error::custom(Error::UnknownOutputOption(el))),
                     }
                 }
 
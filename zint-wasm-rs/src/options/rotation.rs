@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Clockwise rotation of the output
+///
+/// With `typst` feature, any multiple of 90 degrees is accepted when
+/// deserializing, as typst angles can be negative or exceed a full turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "i32")]
+#[cfg_attr(not(feature = "typst"), serde(try_from = "i32"))]
+#[cfg_attr(feature = "typst", serde(try_from = "Degrees"))]
+#[repr(i32)]
+pub enum Rotation {
+    /// No rotation
+    #[default]
+    Deg0 = 0,
+    /// Rotate by 90° clockwise
+    Deg90 = 90,
+    /// Rotate by 180°
+    Deg180 = 180,
+    /// Rotate by 270° clockwise (90° counter-clockwise)
+    Deg270 = 270,
+}
+
+impl Rotation {
+    /// Returns rotation angle in degrees, as expected by Zint.
+    pub fn degrees(&self) -> i32 {
+        *self as i32
+    }
+
+    /// Converts any multiple of 90 degrees into rotation, e.g. `-90` into
+    /// [`Rotation::Deg270`] and `450` into [`Rotation::Deg90`].
+    pub fn from_degrees(degrees: i32) -> Result<Self, Error> {
+        Rotation::try_from(degrees.rem_euclid(360)).map_err(|_| Error::UnknownOption {
+            which: "rotation",
+            value: Box::new(degrees),
+        })
+    }
+}
+
+/// Angle in degrees, normalized when converted into [`Rotation`]
+#[cfg(feature = "typst")]
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Degrees(i32);
+
+#[cfg(feature = "typst")]
+impl TryFrom<Degrees> for Rotation {
+    type Error = Error;
+
+    fn try_from(value: Degrees) -> Result<Self, Self::Error> {
+        Rotation::from_degrees(value.0)
+    }
+}
+
+impl From<Rotation> for i32 {
+    fn from(value: Rotation) -> Self {
+        value.degrees()
+    }
+}
+
+impl TryFrom<i32> for Rotation {
+    type Error = Error;
+
+    /// Converts `0`, `90`, `180` or `270` degrees into rotation; use
+    /// [`Rotation::from_degrees`] for other multiples of 90.
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Rotation::Deg0,
+            90 => Rotation::Deg90,
+            180 => Rotation::Deg180,
+            270 => Rotation::Deg270,
+            _ => {
+                return Err(Error::UnknownOption {
+                    which: "rotation",
+                    value: Box::new(value),
+                })
+            }
+        })
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+use super::{capability::CapabilityFlags, symbology::Symbology, Options};
+
+/// Position of a symbol in a Structured Append sequence (`zint_symbol.structapp`)
+///
+/// Structured Append splits data over several linked symbols that are
+/// reassembled by the reader.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawStructuredAppend")]
+pub struct StructuredAppend {
+    index: i32,
+    count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawStructuredAppend {
+    index: i32,
+    count: i32,
+    #[serde(default)]
+    id: Option<String>,
+}
+
+impl TryFrom<RawStructuredAppend> for StructuredAppend {
+    type Error = Error;
+
+    fn try_from(raw: RawStructuredAppend) -> Result<Self, Self::Error> {
+        let result = StructuredAppend::new(raw.index, raw.count)?;
+        match raw.id {
+            Some(id) => result.with_id(id),
+            None => Ok(result),
+        }
+    }
+}
+
+impl StructuredAppend {
+    /// Maximum length of sequence ID
+    pub const MAX_ID_LEN: usize = 32;
+
+    /// Creates a Structured Append position for symbol at 1-based `index`
+    /// in a sequence of `count` symbols.
+    pub fn new(index: i32, count: i32) -> Result<Self, Error> {
+        if count < 2 {
+            return Err(Error::InvalidStructuredAppend {
+                reason: "count must be at least 2",
+            });
+        }
+        if index < 1 || index > count {
+            return Err(Error::InvalidStructuredAppend {
+                reason: "index must be between 1 and count",
+            });
+        }
+        Ok(StructuredAppend {
+            index,
+            count,
+            id: None,
+        })
+    }
+
+    /// Sets ID distinguishing the sequence from other sequences.
+    ///
+    /// Format of the ID depends on symbology and is validated by Zint.
+    pub fn with_id(mut self, id: impl Into<String>) -> Result<Self, Error> {
+        let id = id.into();
+        if !id.is_ascii() || id.len() > Self::MAX_ID_LEN || id.contains('\0') {
+            return Err(Error::InvalidStructuredAppend {
+                reason: "id must be at most 32 ASCII characters",
+            });
+        }
+        self.id = Some(id);
+        Ok(self)
+    }
+
+    /// 1-based position of symbol in the sequence.
+    pub fn index(&self) -> i32 {
+        self.index
+    }
+
+    /// Number of symbols in the sequence.
+    pub fn count(&self) -> i32 {
+        self.count
+    }
+
+    /// ID of the sequence.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the maximum number of symbols in a sequence, or `None` if
+    /// `symbology` doesn't support Structured Append.
+    pub fn max_count(symbology: Symbology) -> Option<usize> {
+        if !symbology.supports(CapabilityFlags::StructApp) {
+            return None;
+        }
+        Some(match symbology {
+            Symbology::QRCode
+            | Symbology::HIBCQR
+            | Symbology::DataMatrix
+            | Symbology::HIBCDM
+            | Symbology::GridMatrix => 16,
+            Symbology::Aztec | Symbology::HIBCAztec => 26,
+            Symbology::MaxiCode | Symbology::Ultra => 8,
+            Symbology::DotCode => 35,
+            Symbology::CodeOne => 128,
+            // PDF417 and MicroPDF417, including HIBC variants
+            _ => 99999,
+        })
+    }
+
+    /// Splits `data` over `count` linked symbols.
+    ///
+    /// Returns a copy of `options` with [`Options::structured_append`] set
+    /// along with the part of `data` encoded by each symbol. Parts are of
+    /// (nearly) equal length; UTF-8 data is never split within a character.
+    ///
+    /// If `id` is `None`, QR Code symbols share the parity of the whole
+    /// `data` as their ID, which Zint would otherwise compute from each
+    /// part separately.
+    pub fn split<'a>(
+        options: &Options,
+        data: &'a [u8],
+        count: usize,
+        id: Option<&str>,
+    ) -> Result<Vec<(Options, &'a [u8])>, Error> {
+        let max_count = Self::max_count(options.symbology)
+            .ok_or(Error::UnsupportedCapability(CapabilityFlags::StructApp))?;
+        if count < 2 || count > max_count {
+            return Err(Error::InvalidStructuredAppend {
+                reason: "count out of range for symbology",
+            });
+        }
+        let text = std::str::from_utf8(data).ok();
+        let mut boundaries = Vec::with_capacity(count + 1);
+        boundaries.push(0);
+        for i in 1..count {
+            let mut at = data.len() * i / count;
+            if let Some(text) = text {
+                while !text.is_char_boundary(at) {
+                    at += 1;
+                }
+            }
+            boundaries.push(at.max(*boundaries.last().unwrap()));
+        }
+        boundaries.push(data.len());
+        if boundaries.windows(2).any(|it| it[0] == it[1]) {
+            return Err(Error::InvalidStructuredAppend {
+                reason: "data too short to split into count symbols",
+            });
+        }
+
+        let parity;
+        let id = match id {
+            None if options.symbology == Symbology::QRCode => {
+                parity = data.iter().fold(0u8, |acc, it| acc ^ it).to_string();
+                Some(parity.as_str())
+            }
+            _ => id,
+        };
+
+        boundaries
+            .windows(2)
+            .enumerate()
+            .map(|(i, range)| {
+                let mut structured_append = StructuredAppend::new(i as i32 + 1, count as i32)?;
+                if let Some(id) = id {
+                    structured_append = structured_append.with_id(id)?;
+                }
+                let mut options = options.clone();
+                options.structured_append = Some(structured_append);
+                Ok((options, &data[range[0]..range[1]]))
+            })
+            .collect()
+    }
+}
@@ -1,129 +1,342 @@
-use serde::Deserialize;
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de::Visitor, Deserialize, Serialize};
 use zint_wasm_sys::*;
 
-#[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, Clone, Copy, Default, Deserialize)]
-#[serde(tag = "symbology", rename_all = "PascalCase")]
-#[non_exhaustive]
-#[repr(i32)]
-pub enum Symbology {
-    Code11 = BARCODE_CODE11 as i32,
-    #[serde(alias = "C25Matrix")]
-    C25Standard = BARCODE_C25STANDARD as i32,
-    C25Inter = BARCODE_C25INTER as i32,
-    C25IATA = BARCODE_C25IATA as i32,
-    C25Logic = BARCODE_C25LOGIC as i32,
-    C25Ind = BARCODE_C25IND as i32,
-    Code39 = BARCODE_CODE39 as i32,
-    ExCode39 = BARCODE_EXCODE39 as i32,
-    EANX = BARCODE_EANX as i32,
-    EANXChk = BARCODE_EANX_CHK as i32,
-    #[serde(alias = "EAN128")]
-    GS1128 = BARCODE_GS1_128 as i32,
-    Codabar = BARCODE_CODABAR as i32,
+use crate::{error::Error, util::cstr_to_string};
+
+use super::capability::{CapabilityFlags, CapabilitySet};
+
+/// Broad shape of symbols produced by a symbology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbologyCategory {
+    /// Single row of bars
+    Linear,
+    /// Several rows of linear symbols stacked on top of each other
+    Stacked,
+    /// Two-dimensional grid of modules
+    Matrix,
+    /// Height-modulated (e.g. 4-state) postal barcodes
+    Postal,
+    /// Linear symbol with a 2D component stacked above it
+    Composite,
+}
+
+macro_rules! symbologies {
+    ($($(#[$attr: meta])* $name: ident = $id: ident, $category: ident, $title: literal, [$($alias: literal),*];)+) => {
+        #[allow(clippy::upper_case_acronyms)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        #[repr(i32)]
+        pub enum Symbology {
+            $(
+                #[doc = $title]
+                $(#[$attr])*
+                $name = $id as i32,
+            )+
+        }
+
+        impl Symbology {
+            /// All supported symbologies, ordered by Zint ID.
+            pub const ALL: &'static [Symbology] = &[$(Symbology::$name),+];
+
+            /// Returns the canonical name of the symbology, as used by
+            /// [`Display`] and deserialization (e.g. `"QRCode"`).
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Symbology::$name => stringify!($name),)+
+                }
+            }
+
+            /// Returns a human readable name of the symbology (e.g. `"QR Code"`).
+            pub fn title(&self) -> &'static str {
+                match self {
+                    $(Symbology::$name => $title,)+
+                }
+            }
+
+            /// Returns the shape of symbols produced by the symbology.
+            pub fn category(&self) -> SymbologyCategory {
+                match self {
+                    $(Symbology::$name => SymbologyCategory::$category,)+
+                }
+            }
+
+            /// Returns alternative (legacy) names accepted when parsing.
+            pub fn aliases(&self) -> &'static [&'static str] {
+                match self {
+                    $(Symbology::$name => &[$($alias),*],)+
+                }
+            }
+
+            /// Name of the Zint `#define`, without the `BARCODE_` prefix.
+            fn define_name(&self) -> &'static str {
+                match self {
+                    $(Symbology::$name => &stringify!($id)["BARCODE_".len()..],)+
+                }
+            }
+        }
+    };
+}
+
+symbologies! {
+    Code11 = BARCODE_CODE11, Linear, "Code 11", [];
+    C25Standard = BARCODE_C25STANDARD, Linear, "Standard Code 2 of 5", ["C25Matrix"];
+    C25Inter = BARCODE_C25INTER, Linear, "Interleaved 2 of 5", [];
+    C25IATA = BARCODE_C25IATA, Linear, "Code 2 of 5 IATA", [];
+    C25Logic = BARCODE_C25LOGIC, Linear, "Code 2 of 5 Data Logic", [];
+    C25Ind = BARCODE_C25IND, Linear, "Code 2 of 5 Industrial", [];
+    Code39 = BARCODE_CODE39, Linear, "Code 39", [];
+    ExCode39 = BARCODE_EXCODE39, Linear, "Extended Code 39", [];
+    EANX = BARCODE_EANX, Linear, "EAN", [];
+    EANXChk = BARCODE_EANX_CHK, Linear, "EAN + Check Digit", [];
+    GS1128 = BARCODE_GS1_128, Linear, "GS1-128", ["EAN128"];
+    Codabar = BARCODE_CODABAR, Linear, "Codabar", [];
     #[default]
-    Code128 = BARCODE_CODE128 as i32,
-    DPLEIT = BARCODE_DPLEIT as i32,
-    DPIDENT = BARCODE_DPIDENT as i32,
-    Code16k = BARCODE_CODE16K as i32,
-    Code49 = BARCODE_CODE49 as i32,
-    Code93 = BARCODE_CODE93 as i32,
-    Flat = BARCODE_FLAT as i32,
-    #[serde(alias = "RSS14")]
-    DBarOmn = BARCODE_DBAR_OMN as i32,
-    #[serde(alias = "RSSLtd")]
-    DBarLtd = BARCODE_DBAR_LTD as i32,
-    #[serde(alias = "RSSExp")]
-    DBarExp = BARCODE_DBAR_EXP as i32,
-    Telepen = BARCODE_TELEPEN as i32,
-    UPCA = BARCODE_UPCA as i32,
-    UPCAChk = BARCODE_UPCA_CHK as i32,
-    UPCE = BARCODE_UPCE as i32,
-    UPCEChk = BARCODE_UPCE_CHK as i32,
-    Postnet = BARCODE_POSTNET as i32,
-    MSIPlessey = BARCODE_MSI_PLESSEY as i32,
-    FIM = BARCODE_FIM as i32,
-    Logmars = BARCODE_LOGMARS as i32,
-    Pharma = BARCODE_PHARMA as i32,
-    PZN = BARCODE_PZN as i32,
-    PharmaTwo = BARCODE_PHARMA_TWO as i32,
-    CEPNet = BARCODE_CEPNET as i32,
-    PDF417 = BARCODE_PDF417 as i32,
-    #[serde(alias = "PDF417Trunc")]
-    PDF417Comp = BARCODE_PDF417COMP as i32,
-    MaxiCode = BARCODE_MAXICODE as i32,
-    QRCode = BARCODE_QRCODE as i32,
-    #[serde(alias = "Code128B")]
-    Code128AB = BARCODE_CODE128AB as i32,
-    AusPost = BARCODE_AUSPOST as i32,
-    AusReply = BARCODE_AUSREPLY as i32,
-    AusRoute = BARCODE_AUSROUTE as i32,
-    AusRedirect = BARCODE_AUSREDIRECT as i32,
-    ISBNX = BARCODE_ISBNX as i32,
-    RM4SCC = BARCODE_RM4SCC as i32,
-    DataMatrix = BARCODE_DATAMATRIX as i32,
-    EAN14 = BARCODE_EAN14 as i32,
-    VIN = BARCODE_VIN as i32,
-    CodablockF = BARCODE_CODABLOCKF as i32,
-    NVE18 = BARCODE_NVE18 as i32,
-    JapanPost = BARCODE_JAPANPOST as i32,
-    KoreaPost = BARCODE_KOREAPOST as i32,
-    #[serde(alias = "RSS14Stack")]
-    DBarStk = BARCODE_DBAR_STK as i32,
-    #[serde(alias = "RSS14StackOmni")]
-    DBarOmnStk = BARCODE_DBAR_OMNSTK as i32,
-    #[serde(alias = "RSSExpStack")]
-    DBarExpStk = BARCODE_DBAR_EXPSTK as i32,
-    Planet = BARCODE_PLANET as i32,
-    MicroPDF417 = BARCODE_MICROPDF417 as i32,
-    #[serde(alias = "OneCode")]
-    USPSIMail = BARCODE_USPS_IMAIL as i32,
-    Plessey = BARCODE_PLESSEY as i32,
-    TelepenNum = BARCODE_TELEPEN_NUM as i32,
-    ITF14 = BARCODE_ITF14 as i32,
-    KIX = BARCODE_KIX as i32,
-    Aztec = BARCODE_AZTEC as i32,
-    DAFT = BARCODE_DAFT as i32,
-    DPD = BARCODE_DPD as i32,
-    MicroQR = BARCODE_MICROQR as i32,
-    HIBC128 = BARCODE_HIBC_128 as i32,
-    HIBC39 = BARCODE_HIBC_39 as i32,
-    HIBCDM = BARCODE_HIBC_DM as i32,
-    HIBCQR = BARCODE_HIBC_QR as i32,
-    HIBCPDF = BARCODE_HIBC_PDF as i32,
-    HIBCMicPDF = BARCODE_HIBC_MICPDF as i32,
-    HIBCCodablockF = BARCODE_HIBC_BLOCKF as i32,
-    HIBCAztec = BARCODE_HIBC_AZTEC as i32,
-    DotCode = BARCODE_DOTCODE as i32,
-    HanXin = BARCODE_HANXIN as i32,
-    Mailmark2D = BARCODE_MAILMARK_2D as i32,
-    UPUS10 = BARCODE_UPU_S10 as i32,
-    #[serde(alias = "Mailmark")]
-    Mailmark4S = BARCODE_MAILMARK_4S as i32,
-    AzRune = BARCODE_AZRUNE as i32,
-    Code32 = BARCODE_CODE32 as i32,
-    EANXCC = BARCODE_EANX_CC as i32,
-    #[serde(alias = "EAN128CC")]
-    GS1128CC = BARCODE_GS1_128_CC as i32,
-    #[serde(alias = "RSS14CC")]
-    DBarOmnCC = BARCODE_DBAR_OMN_CC as i32,
-    #[serde(alias = "RSSLtdCC")]
-    DBarLtdCC = BARCODE_DBAR_LTD_CC as i32,
-    #[serde(alias = "RSSExpCC")]
-    DBarExpCC = BARCODE_DBAR_EXP_CC as i32,
-    UPCACC = BARCODE_UPCA_CC as i32,
-    UPCECC = BARCODE_UPCE_CC as i32,
-    #[serde(alias = "RSS14StackCC")]
-    DBarStkCC = BARCODE_DBAR_STK_CC as i32,
-    #[serde(alias = "RSS14OmniCC")]
-    DBarOmnStkCC = BARCODE_DBAR_OMNSTK_CC as i32,
-    #[serde(alias = "RSSExpStackCC")]
-    DBarExpStkCC = BARCODE_DBAR_EXPSTK_CC as i32,
-    Channel = BARCODE_CHANNEL as i32,
-    CodeOne = BARCODE_CODEONE as i32,
-    GridMatrix = BARCODE_GRIDMATRIX as i32,
-    UPNQR = BARCODE_UPNQR as i32,
-    Ultra = BARCODE_ULTRA as i32,
-    RMQR = BARCODE_RMQR as i32,
-    BC412 = BARCODE_LAST as i32,
+    Code128 = BARCODE_CODE128, Linear, "Code 128", [];
+    DPLEIT = BARCODE_DPLEIT, Linear, "Deutsche Post Leitcode", [];
+    DPIDENT = BARCODE_DPIDENT, Linear, "Deutsche Post Identcode", [];
+    Code16k = BARCODE_CODE16K, Stacked, "Code 16K", [];
+    Code49 = BARCODE_CODE49, Stacked, "Code 49", [];
+    Code93 = BARCODE_CODE93, Linear, "Code 93", [];
+    Flat = BARCODE_FLAT, Linear, "Flattermarken", [];
+    DBarOmn = BARCODE_DBAR_OMN, Linear, "GS1 DataBar Omnidirectional", ["RSS14"];
+    DBarLtd = BARCODE_DBAR_LTD, Linear, "GS1 DataBar Limited", ["RSSLtd"];
+    DBarExp = BARCODE_DBAR_EXP, Linear, "GS1 DataBar Expanded", ["RSSExp"];
+    Telepen = BARCODE_TELEPEN, Linear, "Telepen Alpha", [];
+    UPCA = BARCODE_UPCA, Linear, "UPC-A", [];
+    UPCAChk = BARCODE_UPCA_CHK, Linear, "UPC-A + Check Digit", [];
+    UPCE = BARCODE_UPCE, Linear, "UPC-E", [];
+    UPCEChk = BARCODE_UPCE_CHK, Linear, "UPC-E + Check Digit", [];
+    Postnet = BARCODE_POSTNET, Postal, "POSTNET", [];
+    MSIPlessey = BARCODE_MSI_PLESSEY, Linear, "MSI Plessey", [];
+    FIM = BARCODE_FIM, Postal, "Facing Identification Mark", [];
+    Logmars = BARCODE_LOGMARS, Linear, "LOGMARS", [];
+    Pharma = BARCODE_PHARMA, Linear, "Pharmacode One-Track", [];
+    PZN = BARCODE_PZN, Linear, "Pharmazentralnummer", [];
+    PharmaTwo = BARCODE_PHARMA_TWO, Linear, "Pharmacode Two-Track", [];
+    CEPNet = BARCODE_CEPNET, Postal, "Brazilian CEPNet", [];
+    PDF417 = BARCODE_PDF417, Stacked, "PDF417", [];
+    PDF417Comp = BARCODE_PDF417COMP, Stacked, "Compact PDF417", ["PDF417Trunc"];
+    MaxiCode = BARCODE_MAXICODE, Matrix, "MaxiCode", [];
+    QRCode = BARCODE_QRCODE, Matrix, "QR Code", [];
+    Code128AB = BARCODE_CODE128AB, Linear, "Code 128 (Suppress Code Set C)", ["Code128B"];
+    AusPost = BARCODE_AUSPOST, Postal, "Australia Post Standard Customer", [];
+    AusReply = BARCODE_AUSREPLY, Postal, "Australia Post Reply Paid", [];
+    AusRoute = BARCODE_AUSROUTE, Postal, "Australia Post Routing", [];
+    AusRedirect = BARCODE_AUSREDIRECT, Postal, "Australia Post Redirection", [];
+    ISBNX = BARCODE_ISBNX, Linear, "ISBN", [];
+    RM4SCC = BARCODE_RM4SCC, Postal, "Royal Mail 4-State Customer Code", [];
+    DataMatrix = BARCODE_DATAMATRIX, Matrix, "Data Matrix", [];
+    EAN14 = BARCODE_EAN14, Linear, "EAN-14", [];
+    VIN = BARCODE_VIN, Linear, "Vehicle Identification Number", [];
+    CodablockF = BARCODE_CODABLOCKF, Stacked, "Codablock-F", [];
+    NVE18 = BARCODE_NVE18, Linear, "NVE-18 (SSCC-18)", [];
+    JapanPost = BARCODE_JAPANPOST, Postal, "Japanese Postal Code", [];
+    KoreaPost = BARCODE_KOREAPOST, Postal, "Korea Post", [];
+    DBarStk = BARCODE_DBAR_STK, Stacked, "GS1 DataBar Stacked", ["RSS14Stack"];
+    DBarOmnStk = BARCODE_DBAR_OMNSTK, Stacked, "GS1 DataBar Stacked Omnidirectional", ["RSS14StackOmni"];
+    DBarExpStk = BARCODE_DBAR_EXPSTK, Stacked, "GS1 DataBar Expanded Stacked", ["RSSExpStack"];
+    Planet = BARCODE_PLANET, Postal, "PLANET", [];
+    MicroPDF417 = BARCODE_MICROPDF417, Stacked, "MicroPDF417", [];
+    USPSIMail = BARCODE_USPS_IMAIL, Postal, "USPS Intelligent Mail", ["OneCode"];
+    Plessey = BARCODE_PLESSEY, Linear, "UK Plessey", [];
+    TelepenNum = BARCODE_TELEPEN_NUM, Linear, "Telepen Numeric", [];
+    ITF14 = BARCODE_ITF14, Linear, "ITF-14", [];
+    KIX = BARCODE_KIX, Postal, "Dutch Post KIX Code", [];
+    Aztec = BARCODE_AZTEC, Matrix, "Aztec Code", [];
+    DAFT = BARCODE_DAFT, Postal, "DAFT Code", [];
+    DPD = BARCODE_DPD, Linear, "DPD Code", [];
+    MicroQR = BARCODE_MICROQR, Matrix, "Micro QR Code", [];
+    HIBC128 = BARCODE_HIBC_128, Linear, "HIBC Code 128", [];
+    HIBC39 = BARCODE_HIBC_39, Linear, "HIBC Code 39", [];
+    HIBCDM = BARCODE_HIBC_DM, Matrix, "HIBC Data Matrix", [];
+    HIBCQR = BARCODE_HIBC_QR, Matrix, "HIBC QR Code", [];
+    HIBCPDF = BARCODE_HIBC_PDF, Stacked, "HIBC PDF417", [];
+    HIBCMicPDF = BARCODE_HIBC_MICPDF, Stacked, "HIBC MicroPDF417", [];
+    HIBCCodablockF = BARCODE_HIBC_BLOCKF, Stacked, "HIBC Codablock-F", [];
+    HIBCAztec = BARCODE_HIBC_AZTEC, Matrix, "HIBC Aztec Code", [];
+    DotCode = BARCODE_DOTCODE, Matrix, "DotCode", [];
+    HanXin = BARCODE_HANXIN, Matrix, "Han Xin Code", [];
+    Mailmark2D = BARCODE_MAILMARK_2D, Matrix, "Royal Mail 2D Mailmark", [];
+    UPUS10 = BARCODE_UPU_S10, Linear, "UPU S10", [];
+    Mailmark4S = BARCODE_MAILMARK_4S, Postal, "Royal Mail 4-State Mailmark", ["Mailmark"];
+    AzRune = BARCODE_AZRUNE, Matrix, "Aztec Runes", [];
+    Code32 = BARCODE_CODE32, Linear, "Code 32", [];
+    EANXCC = BARCODE_EANX_CC, Composite, "EAN Composite", [];
+    GS1128CC = BARCODE_GS1_128_CC, Composite, "GS1-128 Composite", ["EAN128CC"];
+    DBarOmnCC = BARCODE_DBAR_OMN_CC, Composite, "GS1 DataBar Omnidirectional Composite", ["RSS14CC"];
+    DBarLtdCC = BARCODE_DBAR_LTD_CC, Composite, "GS1 DataBar Limited Composite", ["RSSLtdCC"];
+    DBarExpCC = BARCODE_DBAR_EXP_CC, Composite, "GS1 DataBar Expanded Composite", ["RSSExpCC"];
+    UPCACC = BARCODE_UPCA_CC, Composite, "UPC-A Composite", [];
+    UPCECC = BARCODE_UPCE_CC, Composite, "UPC-E Composite", [];
+    DBarStkCC = BARCODE_DBAR_STK_CC, Composite, "GS1 DataBar Stacked Composite", ["RSS14StackCC"];
+    DBarOmnStkCC = BARCODE_DBAR_OMNSTK_CC, Composite, "GS1 DataBar Stacked Omnidirectional Composite", ["RSS14OmniCC"];
+    DBarExpStkCC = BARCODE_DBAR_EXPSTK_CC, Composite, "GS1 DataBar Expanded Stacked Composite", ["RSSExpStackCC"];
+    Channel = BARCODE_CHANNEL, Linear, "Channel Code", [];
+    CodeOne = BARCODE_CODEONE, Matrix, "Code One", [];
+    GridMatrix = BARCODE_GRIDMATRIX, Matrix, "Grid Matrix", [];
+    UPNQR = BARCODE_UPNQR, Matrix, "UPNQR", [];
+    Ultra = BARCODE_ULTRA, Matrix, "Ultracode", [];
+    RMQR = BARCODE_RMQR, Matrix, "Rectangular Micro QR Code", [];
+    BC412 = BARCODE_BC412, Linear, "IBM BC412", [];
+}
+
+/// Keeps only ASCII alphanumeric characters, uppercased.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|it| it.to_ascii_uppercase())
+        .collect()
+}
+
+impl Symbology {
+    /// Returns the Zint ID of the symbology.
+    pub fn id(&self) -> i32 {
+        *self as i32
+    }
+
+    /// Returns the name Zint uses for the symbology (e.g. `"BARCODE_QRCODE"`).
+    pub fn barcode_name(&self) -> String {
+        let mut name = [0; 32];
+        unsafe {
+            // Safety: zint writes at most 32 bytes including nul terminator
+            ZBarcode_BarcodeName(self.id(), name.as_mut_ptr());
+        }
+        cstr_to_string(&name)
+    }
+
+    /// Returns default X-dimension of the symbology in millimeters.
+    pub fn default_x_dimension_mm(&self) -> f32 {
+        unsafe { ZBarcode_Default_Xdim(self.id()) }
+    }
+
+    /// Returns whether `id` is a symbology ID recognized by Zint.
+    pub fn is_valid_id(id: i32) -> bool {
+        unsafe { ZBarcode_ValidID(id) != 0 }
+    }
+
+    /// Returns capabilities supported by the symbology.
+    pub fn capabilities(&self) -> CapabilitySet {
+        CapabilitySet::query(*self as i32)
+    }
+
+    /// Returns whether the symbology supports `capability`.
+    pub fn supports(&self, capability: CapabilityFlags) -> bool {
+        self.capabilities().supports(capability)
+    }
+}
+
+impl From<Symbology> for i32 {
+    fn from(symbology: Symbology) -> Self {
+        symbology.id()
+    }
+}
+
+impl TryFrom<i32> for Symbology {
+    type Error = Error;
+
+    fn try_from(id: i32) -> Result<Self, Self::Error> {
+        Symbology::ALL
+            .iter()
+            .find(|it| it.id() == id)
+            .copied()
+            .ok_or_else(|| Error::UnknownOption {
+                which: "symbology",
+                value: Box::new(id),
+            })
+    }
+}
+
+impl Display for Symbology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Symbology {
+    type Err = Error;
+
+    /// Parses a symbology from its name, title, alias, Zint define name
+    /// (with or without `BARCODE_` prefix) or numeric ID.
+    ///
+    /// Matching ignores case and any non-alphanumeric characters, so
+    /// `"QRCode"`, `"qr-code"`, `"QR Code"` and `"BARCODE_QRCODE"` are all
+    /// equivalent.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = s.trim().parse::<i32>() {
+            return Symbology::try_from(id);
+        }
+        let name = match s.trim().get(.."BARCODE_".len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("BARCODE_") => &s.trim()[prefix.len()..],
+            _ => s,
+        };
+        let name = normalize_name(name);
+        Symbology::ALL
+            .iter()
+            .find(|it| {
+                [it.name(), it.title(), it.define_name()]
+                    .into_iter()
+                    .chain(it.aliases().iter().copied())
+                    .any(|candidate| normalize_name(candidate) == name)
+            })
+            .copied()
+            .ok_or_else(|| Error::UnknownOption {
+                which: "symbology",
+                value: Box::new(s.to_string()),
+            })
+    }
+}
+
+impl Serialize for Symbology {
+    /// Serializes symbology as its [`name`](Symbology::name).
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbology {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SymbologyVisitor;
+        impl<'de> Visitor<'de> for SymbologyVisitor {
+            type Value = Symbology;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a symbology name or numeric ID")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Symbology::from_str(v).map_err(E::custom)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .map_err(E::custom)
+                    .and_then(|id| Symbology::try_from(id).map_err(E::custom))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .map_err(E::custom)
+                    .and_then(|id| Symbology::try_from(id).map_err(E::custom))
+            }
+        }
+
+        deserializer.deserialize_any(SymbologyVisitor)
+    }
 }
@@ -0,0 +1,770 @@
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+use super::symbology::Symbology;
+
+/// QR Code error correction level (`option_1`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum QrEcc {
+    /// Recovers ~7% of data (L)
+    L = 1,
+    /// Recovers ~15% of data (M)
+    M = 2,
+    /// Recovers ~25% of data (Q)
+    Q = 3,
+    /// Recovers ~30% of data (H)
+    H = 4,
+}
+
+impl QrEcc {
+    /// Returns whether `symbology` supports the level.
+    pub fn supported_by(&self, symbology: Symbology) -> bool {
+        match symbology {
+            Symbology::QRCode | Symbology::HIBCQR => true,
+            Symbology::MicroQR => *self != QrEcc::H,
+            Symbology::RMQR => matches!(self, QrEcc::M | QrEcc::H),
+            _ => false,
+        }
+    }
+}
+
+/// Aztec Code error correction level (`option_1`), as percentage of
+/// symbol capacity (plus 3 codewords)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum AztecEcc {
+    Percent10 = 1,
+    Percent23 = 2,
+    Percent36 = 3,
+    Percent50 = 4,
+}
+
+/// MSI Plessey check digit scheme (`option_2`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(i32)]
+pub enum MsiCheckDigit {
+    #[default]
+    None = 0,
+    Mod10 = 1,
+    Mod10Mod10 = 2,
+    /// Modulo 11 with IBM weighting
+    Mod11 = 3,
+    /// Modulo 11 with IBM weighting followed by modulo 10
+    Mod11Mod10 = 4,
+    /// Modulo 11 with NCR weighting
+    Mod11Ncr = 5,
+    /// Modulo 11 with NCR weighting followed by modulo 10
+    Mod11NcrMod10 = 6,
+}
+
+/// Number of Code 11 check digits (`option_2`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(i32)]
+pub enum Code11CheckDigits {
+    /// Two check digits (C and K)
+    #[default]
+    Two = 0,
+    /// Single check digit (C)
+    One = 1,
+    None = 2,
+}
+
+/// Optional check digit of Code 39 and Code 2 of 5 symbologies (`option_2`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(i32)]
+pub enum CheckDigit {
+    #[default]
+    None = 0,
+    /// Add check digit and show it in Human Readable Text
+    Visible = 1,
+    /// Add check digit but don't show it in Human Readable Text (Code 2 of 5
+    /// only)
+    Hidden = 2,
+}
+
+/// QR Code, HIBC QR, Micro QR and rMQR options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QrOptions {
+    pub ecc: Option<QrEcc>,
+    /// Symbol version (`1`-`40` for QR Code, `1`-`4` for Micro QR and
+    /// `1`-`38` for rMQR)
+    pub version: Option<i32>,
+}
+
+/// PDF417 and MicroPDF417 options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pdf417Options {
+    /// Error correction level (`0`-`8`; PDF417 only)
+    pub ecc: Option<i32>,
+    /// Number of data columns (`1`-`30` for PDF417, `1`-`4` for MicroPDF417)
+    pub columns: Option<i32>,
+}
+
+/// Aztec Code options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AztecOptions {
+    pub ecc: Option<AztecEcc>,
+    /// Symbol size (`1`-`4` for compact, `5`-`36` for full-range symbols)
+    pub layers: Option<i32>,
+}
+
+/// Data Matrix, Han Xin, Grid Matrix, Code One and Ultracode options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatrixOptions {
+    /// Error correction level (`1`-`4` for Han Xin, `1`-`5` for Grid Matrix,
+    /// `1`-`6` for Ultracode)
+    pub ecc: Option<i32>,
+    /// Symbol version or size (`1`-`48` for Data Matrix, `1`-`84` for Han
+    /// Xin, `1`-`13` for Grid Matrix, `1`-`10` for Code One)
+    pub version: Option<i32>,
+}
+
+/// DotCode options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DotCodeOptions {
+    /// Number of columns (`5`-`200`)
+    pub columns: Option<i32>,
+}
+
+/// MaxiCode options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaxiCodeOptions {
+    /// Mode (`2`-`6`)
+    pub mode: Option<i32>,
+}
+
+/// MSI Plessey options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MsiOptions {
+    pub check_digit: MsiCheckDigit,
+}
+
+/// Code 11 options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Code11Options {
+    pub check_digits: Code11CheckDigits,
+}
+
+/// Code 39, Extended Code 39, LOGMARS and Code 2 of 5 options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckDigitOptions {
+    pub check_digit: CheckDigit,
+}
+
+/// EAN, UPC and ISBN options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EanUpcOptions {
+    /// Gap between main symbol and add-on in X-dimensions (`7`-`12`; `9`-`12`
+    /// for UPC-A)
+    pub addon_gap: Option<i32>,
+}
+
+/// Codablock-F options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodablockFOptions {
+    /// Number of rows (`1`-`44`)
+    pub rows: Option<i32>,
+    /// Number of columns (`9`-`67`)
+    pub columns: Option<i32>,
+}
+
+/// Typed symbology specific options, mapped onto `option_1` and `option_2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SymbologyOptions {
+    Qr(QrOptions),
+    Pdf417(Pdf417Options),
+    Aztec(AztecOptions),
+    Matrix(MatrixOptions),
+    DotCode(DotCodeOptions),
+    MaxiCode(MaxiCodeOptions),
+    Msi(MsiOptions),
+    Code11(Code11Options),
+    CheckDigit(CheckDigitOptions),
+    EanUpc(EanUpcOptions),
+    CodablockF(CodablockFOptions),
+}
+
+macro_rules! from_options {
+    ($($variant: ident($options: ty),)+) => {
+        $(
+            impl From<$options> for SymbologyOptions {
+                fn from(options: $options) -> Self {
+                    SymbologyOptions::$variant(options)
+                }
+            }
+        )+
+    };
+}
+
+from_options! {
+    Qr(QrOptions),
+    Pdf417(Pdf417Options),
+    Aztec(AztecOptions),
+    Matrix(MatrixOptions),
+    DotCode(DotCodeOptions),
+    MaxiCode(MaxiCodeOptions),
+    Msi(MsiOptions),
+    Code11(Code11Options),
+    CheckDigit(CheckDigitOptions),
+    EanUpc(EanUpcOptions),
+    CodablockF(CodablockFOptions),
+}
+
+/// Returns range of versions (sizes) selectable with `option_2`, or `None` if
+/// `symbology` doesn't have versions.
+pub(crate) fn version_range(symbology: Symbology) -> Option<RangeInclusive<i32>> {
+    Some(match symbology {
+        Symbology::QRCode | Symbology::HIBCQR => 1..=40,
+        Symbology::MicroQR => 1..=4,
+        Symbology::RMQR => 1..=38,
+        Symbology::Aztec | Symbology::HIBCAztec => 1..=36,
+        Symbology::HanXin => 1..=84,
+        Symbology::GridMatrix => 1..=13,
+        Symbology::DataMatrix | Symbology::HIBCDM => 1..=48,
+        Symbology::CodeOne => 1..=10,
+        _ => return None,
+    })
+}
+
+/// Returns an error if `value` isn't in `range`.
+pub(crate) fn check_range(
+    which: &'static str,
+    value: i32,
+    range: RangeInclusive<i32>,
+    symbology: Symbology,
+) -> Result<(), Error> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(Error::OutOfRange {
+            which,
+            value: value as f32,
+            range: *range.start() as f32..=*range.end() as f32,
+            symbology,
+        })
+    }
+}
+
+impl SymbologyOptions {
+    /// Returns an error if options can't be used with `symbology` or any
+    /// value is out of range.
+    pub fn check(&self, symbology: Symbology) -> Result<(), Error> {
+        let incompatible = || Error::IncompatibleOption {
+            which: self.name(),
+            symbology,
+        };
+        let check_optional = |which, value: Option<i32>, range| match value {
+            Some(value) => check_range(which, value, range, symbology),
+            None => Ok(()),
+        };
+
+        match self {
+            SymbologyOptions::Qr(options) => {
+                let versions = match symbology {
+                    Symbology::QRCode
+                    | Symbology::HIBCQR
+                    | Symbology::MicroQR
+                    | Symbology::RMQR => version_range(symbology).unwrap(),
+                    _ => return Err(incompatible()),
+                };
+                if let Some(ecc) = options.ecc {
+                    if !ecc.supported_by(symbology) {
+                        return Err(Error::UnsupportedEcc { ecc, symbology });
+                    }
+                }
+                check_optional("version", options.version, versions)
+            }
+            SymbologyOptions::Pdf417(options) => {
+                let (ecc, columns) = match symbology {
+                    Symbology::PDF417 | Symbology::PDF417Comp | Symbology::HIBCPDF => {
+                        (Some(0..=8), 1..=30)
+                    }
+                    Symbology::MicroPDF417 | Symbology::HIBCMicPDF => (None, 1..=4),
+                    _ => return Err(incompatible()),
+                };
+                match (ecc, options.ecc) {
+                    (Some(range), value) => check_optional("ecc", value, range)?,
+                    (None, Some(_)) => {
+                        return Err(Error::IncompatibleOption {
+                            which: "ecc",
+                            symbology,
+                        })
+                    }
+                    (None, None) => {}
+                }
+                check_optional("columns", options.columns, columns)
+            }
+            SymbologyOptions::Aztec(options) => {
+                if !matches!(symbology, Symbology::Aztec | Symbology::HIBCAztec) {
+                    return Err(incompatible());
+                }
+                check_optional("layers", options.layers, 1..=36)
+            }
+            SymbologyOptions::Matrix(options) => {
+                let ecc = match symbology {
+                    Symbology::HanXin => Some(1..=4),
+                    Symbology::GridMatrix => Some(1..=5),
+                    Symbology::Ultra => Some(1..=6),
+                    Symbology::DataMatrix | Symbology::HIBCDM | Symbology::CodeOne => None,
+                    _ => return Err(incompatible()),
+                };
+                match (ecc, options.ecc) {
+                    (Some(range), value) => check_optional("ecc", value, range)?,
+                    (None, Some(_)) => {
+                        return Err(Error::IncompatibleOption {
+                            which: "ecc",
+                            symbology,
+                        })
+                    }
+                    (None, None) => {}
+                }
+                match (version_range(symbology), options.version) {
+                    (Some(range), value) => check_optional("version", value, range),
+                    (None, Some(_)) => Err(Error::IncompatibleOption {
+                        which: "version",
+                        symbology,
+                    }),
+                    (None, None) => Ok(()),
+                }
+            }
+            SymbologyOptions::DotCode(options) => {
+                if symbology != Symbology::DotCode {
+                    return Err(incompatible());
+                }
+                check_optional("columns", options.columns, 5..=200)
+            }
+            SymbologyOptions::MaxiCode(options) => {
+                if symbology != Symbology::MaxiCode {
+                    return Err(incompatible());
+                }
+                check_optional("mode", options.mode, 2..=6)
+            }
+            SymbologyOptions::Msi(_) => match symbology {
+                Symbology::MSIPlessey => Ok(()),
+                _ => Err(incompatible()),
+            },
+            SymbologyOptions::Code11(_) => match symbology {
+                Symbology::Code11 => Ok(()),
+                _ => Err(incompatible()),
+            },
+            SymbologyOptions::CheckDigit(options) => match symbology {
+                Symbology::C25Standard
+                | Symbology::C25Inter
+                | Symbology::C25IATA
+                | Symbology::C25Logic
+                | Symbology::C25Ind => Ok(()),
+                Symbology::Code39 | Symbology::ExCode39 | Symbology::Logmars => {
+                    if options.check_digit == CheckDigit::Hidden {
+                        return Err(Error::UnknownOption {
+                            which: "check_digit",
+                            value: Box::new(options.check_digit),
+                        });
+                    }
+                    Ok(())
+                }
+                _ => Err(incompatible()),
+            },
+            SymbologyOptions::EanUpc(options) => {
+                let addon_gap = match symbology {
+                    Symbology::UPCA | Symbology::UPCAChk | Symbology::UPCACC => 9..=12,
+                    Symbology::EANX
+                    | Symbology::EANXChk
+                    | Symbology::EANXCC
+                    | Symbology::UPCE
+                    | Symbology::UPCEChk
+                    | Symbology::UPCECC
+                    | Symbology::ISBNX => 7..=12,
+                    _ => return Err(incompatible()),
+                };
+                check_optional("addon_gap", options.addon_gap, addon_gap)
+            }
+            SymbologyOptions::CodablockF(options) => {
+                if !matches!(symbology, Symbology::CodablockF | Symbology::HIBCCodablockF) {
+                    return Err(incompatible());
+                }
+                check_optional("rows", options.rows, 1..=44)?;
+                check_optional("columns", options.columns, 9..=67)
+            }
+        }
+    }
+
+    /// Returns `option_1` and `option_2` values; `None` leaves Zint defaults.
+    pub fn option_values(&self) -> (Option<i32>, Option<i32>) {
+        match self {
+            SymbologyOptions::Qr(options) => (options.ecc.map(|it| it as i32), options.version),
+            SymbologyOptions::Pdf417(options) => (options.ecc, options.columns),
+            SymbologyOptions::Aztec(options) => (options.ecc.map(|it| it as i32), options.layers),
+            SymbologyOptions::Matrix(options) => (options.ecc, options.version),
+            SymbologyOptions::DotCode(options) => (None, options.columns),
+            SymbologyOptions::MaxiCode(options) => (options.mode, None),
+            SymbologyOptions::Msi(options) => (None, Some(options.check_digit as i32)),
+            SymbologyOptions::Code11(options) => (None, Some(options.check_digits as i32)),
+            SymbologyOptions::CheckDigit(options) => (None, Some(options.check_digit as i32)),
+            SymbologyOptions::EanUpc(options) => (None, options.addon_gap),
+            SymbologyOptions::CodablockF(options) => (options.rows, options.columns),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            SymbologyOptions::Qr(_) => "QR",
+            SymbologyOptions::Pdf417(_) => "PDF417",
+            SymbologyOptions::Aztec(_) => "Aztec",
+            SymbologyOptions::Matrix(_) => "matrix",
+            SymbologyOptions::DotCode(_) => "DotCode",
+            SymbologyOptions::MaxiCode(_) => "MaxiCode",
+            SymbologyOptions::Msi(_) => "MSI",
+            SymbologyOptions::Code11(_) => "Code 11",
+            SymbologyOptions::CheckDigit(_) => "check digit",
+            SymbologyOptions::EanUpc(_) => "EAN/UPC",
+            SymbologyOptions::CodablockF(_) => "Codablock-F",
+        }
+    }
+}
+
+impl Serialize for SymbologyOptions {
+    /// Serializes options as a map of the same readable fields accepted in
+    /// [`Options`](super::Options).
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        RawSymbologyOptions::from(self).serialize(serializer)
+    }
+}
+
+/// Value of a named option that can be written as a bool, a number or a name.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum RawValue {
+    Bool(bool),
+    Number(i64),
+    Name(String),
+}
+
+impl Debug for RawValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawValue::Bool(value) => value.fmt(f),
+            RawValue::Number(value) => value.fmt(f),
+            RawValue::Name(value) => value.fmt(f),
+        }
+    }
+}
+
+impl RawValue {
+    /// Maps value with `parse`, reporting unknown values for option `which`.
+    fn parse<T>(
+        self,
+        which: &'static str,
+        parse: impl FnOnce(&RawValue, &str) -> Option<T>,
+    ) -> Result<T, Error> {
+        let name = match &self {
+            RawValue::Name(name) => name.to_lowercase().replace('_', "-"),
+            _ => String::new(),
+        };
+        parse(&self, &name).ok_or_else(|| Error::UnknownOption {
+            which,
+            value: Box::new(self),
+        })
+    }
+
+    fn number(&self) -> Option<i64> {
+        match self {
+            RawValue::Number(value) => Some(*value),
+            RawValue::Name(name) => name.trim().parse().ok(),
+            RawValue::Bool(_) => None,
+        }
+    }
+}
+
+/// Readable symbology specific options, as written in [`Options`](super::Options),
+/// before they're resolved for a symbology.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "typst", serde(rename_all = "kebab-case"))]
+#[serde(default)]
+pub(crate) struct RawSymbologyOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ecc: Option<RawValue>,
+    #[serde(alias = "size", skip_serializing_if = "Option::is_none")]
+    version: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layers: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    columns: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rows: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check_digit: Option<RawValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    addon_gap: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<i32>,
+}
+
+impl From<&SymbologyOptions> for RawSymbologyOptions {
+    fn from(options: &SymbologyOptions) -> Self {
+        let name = |name: &str| Some(RawValue::Name(name.to_string()));
+        let number = |value: Option<i32>| value.map(|it| RawValue::Number(it as i64));
+        let mut result = RawSymbologyOptions::default();
+        match options {
+            SymbologyOptions::Qr(options) => {
+                result.ecc = options.ecc.and_then(|ecc| {
+                    name(match ecc {
+                        QrEcc::L => "L",
+                        QrEcc::M => "M",
+                        QrEcc::Q => "Q",
+                        QrEcc::H => "H",
+                    })
+                });
+                result.version = options.version;
+            }
+            SymbologyOptions::Pdf417(options) => {
+                result.ecc = number(options.ecc);
+                result.columns = options.columns;
+            }
+            SymbologyOptions::Aztec(options) => {
+                result.ecc = options.ecc.and_then(|ecc| {
+                    name(match ecc {
+                        AztecEcc::Percent10 => "10%",
+                        AztecEcc::Percent23 => "23%",
+                        AztecEcc::Percent36 => "36%",
+                        AztecEcc::Percent50 => "50%",
+                    })
+                });
+                result.layers = options.layers;
+            }
+            SymbologyOptions::Matrix(options) => {
+                result.ecc = number(options.ecc);
+                result.version = options.version;
+            }
+            SymbologyOptions::DotCode(options) => result.columns = options.columns,
+            SymbologyOptions::MaxiCode(options) => result.mode = options.mode,
+            SymbologyOptions::Msi(options) => {
+                result.check_digit = name(match options.check_digit {
+                    MsiCheckDigit::None => "none",
+                    MsiCheckDigit::Mod10 => "mod10",
+                    MsiCheckDigit::Mod10Mod10 => "mod10-mod10",
+                    MsiCheckDigit::Mod11 => "mod11",
+                    MsiCheckDigit::Mod11Mod10 => "mod11-mod10",
+                    MsiCheckDigit::Mod11Ncr => "mod11-ncr",
+                    MsiCheckDigit::Mod11NcrMod10 => "mod11-ncr-mod10",
+                });
+            }
+            SymbologyOptions::Code11(options) => {
+                result.check_digit = name(match options.check_digits {
+                    Code11CheckDigits::Two => "two",
+                    Code11CheckDigits::One => "one",
+                    Code11CheckDigits::None => "none",
+                });
+            }
+            SymbologyOptions::CheckDigit(options) => {
+                result.check_digit = name(match options.check_digit {
+                    CheckDigit::None => "none",
+                    CheckDigit::Visible => "visible",
+                    CheckDigit::Hidden => "hidden",
+                });
+            }
+            SymbologyOptions::EanUpc(options) => result.addon_gap = options.addon_gap,
+            SymbologyOptions::CodablockF(options) => {
+                result.rows = options.rows;
+                result.columns = options.columns;
+            }
+        }
+        result
+    }
+}
+
+impl RawSymbologyOptions {
+    pub(crate) fn resolve(
+        mut self,
+        symbology: Symbology,
+    ) -> Result<Option<SymbologyOptions>, Error> {
+        if self.first_set().is_none() {
+            return Ok(None);
+        }
+
+        let result = match symbology {
+            Symbology::QRCode | Symbology::HIBCQR | Symbology::MicroQR | Symbology::RMQR => {
+                SymbologyOptions::Qr(QrOptions {
+                    ecc: self.take_ecc(|value, name| match (value.number(), name) {
+                        (Some(1), _) | (_, "l") => Some(QrEcc::L),
+                        (Some(2), _) | (_, "m") => Some(QrEcc::M),
+                        (Some(3), _) | (_, "q") => Some(QrEcc::Q),
+                        (Some(4), _) | (_, "h") => Some(QrEcc::H),
+                        _ => None,
+                    })?,
+                    version: self.version.take(),
+                })
+            }
+            Symbology::PDF417
+            | Symbology::PDF417Comp
+            | Symbology::HIBCPDF
+            | Symbology::MicroPDF417
+            | Symbology::HIBCMicPDF => SymbologyOptions::Pdf417(Pdf417Options {
+                ecc: self.take_ecc_level()?,
+                columns: self.columns.take(),
+            }),
+            Symbology::Aztec | Symbology::HIBCAztec => SymbologyOptions::Aztec(AztecOptions {
+                ecc: self.take_ecc(|value, name| {
+                    match (value.number(), name.trim_end_matches('%')) {
+                        (Some(1), _) | (_, "10") => Some(AztecEcc::Percent10),
+                        (Some(2), _) | (_, "23") => Some(AztecEcc::Percent23),
+                        (Some(3), _) | (_, "36") => Some(AztecEcc::Percent36),
+                        (Some(4), _) | (_, "50") => Some(AztecEcc::Percent50),
+                        _ => None,
+                    }
+                })?,
+                layers: self.layers.take().or(self.version.take()),
+            }),
+            Symbology::DataMatrix
+            | Symbology::HIBCDM
+            | Symbology::HanXin
+            | Symbology::GridMatrix
+            | Symbology::CodeOne
+            | Symbology::Ultra => SymbologyOptions::Matrix(MatrixOptions {
+                ecc: self.take_ecc_level()?,
+                version: self.version.take(),
+            }),
+            Symbology::DotCode => SymbologyOptions::DotCode(DotCodeOptions {
+                columns: self.columns.take(),
+            }),
+            Symbology::MaxiCode => SymbologyOptions::MaxiCode(MaxiCodeOptions {
+                mode: self.mode.take(),
+            }),
+            Symbology::MSIPlessey => SymbologyOptions::Msi(MsiOptions {
+                check_digit: self.take_check_digit(|value, name| {
+                    Some(match (value, name) {
+                        (RawValue::Bool(false), _) | (_, "none") => MsiCheckDigit::None,
+                        (RawValue::Bool(true), _) | (_, "mod10") => MsiCheckDigit::Mod10,
+                        (_, "mod10-mod10") => MsiCheckDigit::Mod10Mod10,
+                        (_, "mod11") => MsiCheckDigit::Mod11,
+                        (_, "mod11-mod10") => MsiCheckDigit::Mod11Mod10,
+                        (_, "mod11-ncr") => MsiCheckDigit::Mod11Ncr,
+                        (_, "mod11-ncr-mod10") => MsiCheckDigit::Mod11NcrMod10,
+                        _ => match value.number()? {
+                            0 => MsiCheckDigit::None,
+                            1 => MsiCheckDigit::Mod10,
+                            2 => MsiCheckDigit::Mod10Mod10,
+                            3 => MsiCheckDigit::Mod11,
+                            4 => MsiCheckDigit::Mod11Mod10,
+                            5 => MsiCheckDigit::Mod11Ncr,
+                            6 => MsiCheckDigit::Mod11NcrMod10,
+                            _ => return None,
+                        },
+                    })
+                })?,
+            }),
+            Symbology::Code11 => SymbologyOptions::Code11(Code11Options {
+                check_digits: self.take_check_digit(|value, name| {
+                    match (value, value.number(), name) {
+                        (RawValue::Bool(true), _, _) | (_, Some(2), _) | (_, _, "two") => {
+                            Some(Code11CheckDigits::Two)
+                        }
+                        (_, Some(1), _) | (_, _, "one") => Some(Code11CheckDigits::One),
+                        (RawValue::Bool(false), _, _) | (_, Some(0), _) | (_, _, "none") => {
+                            Some(Code11CheckDigits::None)
+                        }
+                        _ => None,
+                    }
+                })?,
+            }),
+            Symbology::C25Standard
+            | Symbology::C25Inter
+            | Symbology::C25IATA
+            | Symbology::C25Logic
+            | Symbology::C25Ind
+            | Symbology::Code39
+            | Symbology::ExCode39
+            | Symbology::Logmars => SymbologyOptions::CheckDigit(CheckDigitOptions {
+                check_digit: self.take_check_digit(|value, name| {
+                    match (value, value.number(), name) {
+                        (RawValue::Bool(false), _, _) | (_, Some(0), _) | (_, _, "none") => {
+                            Some(CheckDigit::None)
+                        }
+                        (RawValue::Bool(true), _, _)
+                        | (_, Some(1), _)
+                        | (_, _, "visible" | "mod10" | "mod43") => Some(CheckDigit::Visible),
+                        (_, Some(2), _) | (_, _, "hidden") => Some(CheckDigit::Hidden),
+                        _ => None,
+                    }
+                })?,
+            }),
+            Symbology::EANX
+            | Symbology::EANXChk
+            | Symbology::EANXCC
+            | Symbology::UPCA
+            | Symbology::UPCAChk
+            | Symbology::UPCACC
+            | Symbology::UPCE
+            | Symbology::UPCEChk
+            | Symbology::UPCECC
+            | Symbology::ISBNX => SymbologyOptions::EanUpc(EanUpcOptions {
+                addon_gap: self.addon_gap.take(),
+            }),
+            Symbology::CodablockF | Symbology::HIBCCodablockF => {
+                SymbologyOptions::CodablockF(CodablockFOptions {
+                    rows: self.rows.take(),
+                    columns: self.columns.take(),
+                })
+            }
+            _ => {
+                return Err(Error::IncompatibleOption {
+                    which: self.first_set().unwrap(),
+                    symbology,
+                })
+            }
+        };
+
+        // options not used by the symbology
+        if let Some(which) = self.first_set() {
+            return Err(Error::IncompatibleOption { which, symbology });
+        }
+        result.check(symbology)?;
+        Ok(Some(result))
+    }
+
+    /// Returns name of the first option that's set.
+    fn first_set(&self) -> Option<&'static str> {
+        [
+            ("ecc", self.ecc.is_some()),
+            ("version", self.version.is_some()),
+            ("layers", self.layers.is_some()),
+            ("columns", self.columns.is_some()),
+            ("rows", self.rows.is_some()),
+            ("check_digit", self.check_digit.is_some()),
+            ("addon_gap", self.addon_gap.is_some()),
+            ("mode", self.mode.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(name, set)| set.then_some(name))
+    }
+
+    fn take_ecc<T>(
+        &mut self,
+        parse: impl FnOnce(&RawValue, &str) -> Option<T>,
+    ) -> Result<Option<T>, Error> {
+        self.ecc.take().map(|it| it.parse("ecc", parse)).transpose()
+    }
+
+    fn take_ecc_level(&mut self) -> Result<Option<i32>, Error> {
+        self.take_ecc(|value, _| value.number().and_then(|it| i32::try_from(it).ok()))
+    }
+
+    fn take_check_digit<T: Default>(
+        &mut self,
+        parse: impl FnOnce(&RawValue, &str) -> Option<T>,
+    ) -> Result<T, Error> {
+        let check_digit = self
+            .check_digit
+            .take()
+            .map(|it| it.parse("check_digit", parse))
+            .transpose()?;
+        Ok(check_digit.unwrap_or_default())
+    }
+}
@@ -3,104 +3,174 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-use zint_wasm_sys::{zint_symbol, ZBarcode_Encode_and_Print};
+use zint_wasm_sys::{
+    zint_seg, zint_symbol, ZBarcode_Clear, ZBarcode_Encode_Segs_and_Buffer,
+    ZBarcode_Encode_Segs_and_Buffer_Vector, ZBarcode_Encode_Segs_and_Print,
+    ZBarcode_Encode_and_Buffer, ZBarcode_Encode_and_Buffer_Vector, ZBarcode_Encode_and_Print,
+    ZBarcode_Reset, ZBarcode_Scale_From_XdimDp,
+};
 
 use crate::{
-    error::{Error, ZintResult},
-    options::{color::Color, output_options, Options},
+    error::{Error, ZintResult, ZintWarning},
+    matrix::ModuleMatrix,
+    options::{
+        capability::{CapabilityFlags, CapabilitySet},
+        check_scaling,
+        color::Color,
+        output_options,
+        rotation::Rotation,
+        symbology::Symbology,
+        Options,
+    },
+    vector::Vector,
 };
 
-#[repr(transparent)]
+/// Configured Zint symbol.
+///
+/// A symbol can be used to encode any number of payloads; outputs of each
+/// encoding are released and configuration is restored afterwards.
 pub struct Symbol {
     inner: *mut zint_symbol,
+    /// X-dimension in millimeters `scale` is computed from for each output
+    /// format
+    x_dimension: Option<f32>,
+    /// Rotation applied to rendered outputs
+    rotation: Rotation,
 }
 
 impl Symbol {
-    #[allow(clippy::field_reassign_with_default)]
     pub fn new(options: &Options) -> Self {
         let mut result = Self::default();
-        let filename = "res.svg";
+        result.apply_options(options);
+        result
+    }
+
+    /// Resets symbol to default state (`ZBarcode_Reset`) and applies
+    /// provided `options`.
+    pub fn set_options(&mut self, options: &Options) {
+        self.reset();
+        self.apply_options(options);
+    }
+
+    /// Resets all options of the symbol to their defaults.
+    pub fn reset(&mut self) {
+        unsafe {
+            // Safety: Symbol is always created as a valid zint_symbol
+            ZBarcode_Reset(self.inner);
+        }
+        self.x_dimension = None;
+        self.rotation = Rotation::default();
+    }
 
-        result.symbology = options.symbology as i32;
+    fn apply_options(&mut self, options: &Options) {
+        self.symbology = options.symbology.id();
 
         if let Some(height) = options.height {
-            result.height = height;
+            self.height = height;
         }
         if let Some(scale) = options.scale {
-            result.scale = scale;
+            self.scale = scale;
+        }
+        if let Some(dpmm) = options.dpmm {
+            self.dpmm = dpmm;
         }
+        self.x_dimension = match options.x_dimension_mm {
+            Some(x_dimension) => Some(x_dimension),
+            None if options.dpmm.is_some() && options.scale.is_none() => {
+                Some(options.symbology.default_x_dimension_mm())
+            }
+            None => None,
+        };
+        self.rotation = options.rotation.unwrap_or_default();
         if let Some(whitespace_width) = options.whitespace_width {
-            result.whitespace_width = whitespace_width;
+            self.whitespace_width = whitespace_width;
         }
         if let Some(whitespace_height) = options.whitespace_height {
-            result.whitespace_height = whitespace_height;
+            self.whitespace_height = whitespace_height;
         }
         if let Some(border_width) = options.border_width {
-            result.border_width = border_width;
+            self.border_width = border_width;
         }
 
         if let Some(output_options) = options.output_options {
-            result.output_options = output_options.as_i32();
+            self.output_options = output_options.as_i32();
         }
         // Always write to memory file
-        result.output_options |= output_options::OutputOptions::BARCODE_MEMORY_FILE.as_i32();
+        self.output_options |= output_options::OutputOptions::BARCODE_MEMORY_FILE.as_i32();
 
         crate::util::copy_into_cstr(
-            options.fg_color.unwrap_or(Color::BLACK).to_hex_string(),
-            &mut result.fgcolour,
+            options.fg_color.unwrap_or(Color::BLACK).to_zint_string(),
+            &mut self.fgcolour,
         );
 
         crate::util::copy_into_cstr(
             options
                 .bg_color
                 .unwrap_or(Color::TRANSPARENT)
-                .to_hex_string(),
-            &mut result.bgcolour,
+                .to_zint_string(),
+            &mut self.bgcolour,
         );
 
-        crate::util::copy_into_cstr(filename, &mut result.outfile);
-
         if let Some(ref primary) = options.primary {
-            crate::util::copy_into_cstr(primary, &mut result.primary);
+            crate::util::copy_into_cstr(primary, &mut self.primary);
         }
 
         if let Some(option_1) = options.option_1 {
-            result.option_1 = option_1;
+            self.option_1 = option_1;
         }
 
         if let Some(option_2) = options.option_2 {
-            result.option_2 = option_2;
+            self.option_2 = option_2;
+        }
+
+        if let Some(ref symbology_options) = options.symbology_options {
+            let (option_1, option_2) = symbology_options.option_values();
+            if let Some(option_1) = option_1 {
+                self.option_1 = option_1;
+            }
+            if let Some(option_2) = option_2 {
+                self.option_2 = option_2;
+            }
         }
 
         if let Some(option_3) = options.option_3 {
-            result.option_3 = option_3.as_i32();
+            self.option_3 = option_3.as_i32();
         }
 
         if let Some(show_hrt) = options.show_hrt {
-            result.show_hrt = show_hrt as i32;
+            self.show_hrt = show_hrt as i32;
         }
 
         if let Some(ref input_mode) = options.input_mode {
-            result.input_mode = input_mode.as_i32();
+            self.input_mode = input_mode.as_i32();
         }
 
         if let Some(eci) = options.eci {
-            result.eci = eci;
+            self.eci = eci;
         }
 
         if let Some(dot_size) = options.dot_size {
-            result.dot_size = dot_size;
+            self.dot_size = dot_size;
         }
 
         if let Some(text_gap) = options.text_gap {
-            result.text_gap = text_gap;
+            self.text_gap = text_gap;
         }
 
         if let Some(guard_descent) = options.guard_descent {
-            result.guard_descent = guard_descent;
+            self.guard_descent = guard_descent;
         }
 
-        result
+        if let Some(ref structured_append) = options.structured_append {
+            self.structapp.index = structured_append.index();
+            self.structapp.count = structured_append.count();
+            if let Some(id) = structured_append.id() {
+                // ID is NUL-terminated unless it's MAX_ID_LEN long
+                for (dest, src) in self.structapp.id.iter_mut().zip(id.bytes()) {
+                    *dest = src as std::os::raw::c_char;
+                }
+            }
+        }
     }
 
     /// # Safety
@@ -110,35 +180,471 @@ impl Symbol {
         if ptr.is_null() {
             panic!("can't create a Symbol from null pointer")
         }
-        Self { inner: ptr }
+        Self {
+            inner: ptr,
+            x_dimension: None,
+            rotation: Rotation::default(),
+        }
     }
 
     pub fn as_ptr(&self) -> *mut zint_symbol {
         self.inner
     }
 
-    pub fn encode_svg(self, data: &str, length: i32, rotate_angle: i32) -> Result<String, Error> {
-        let c_str_data = CString::new(data).expect("CString::new failed");
-        let result = ZintResult::from(unsafe {
-            ZBarcode_Encode_and_Print(
-                self.inner,
-                c_str_data.as_bytes_with_nul().as_ptr(),
-                length,
-                rotate_angle,
-            ) as u32
-        });
-        if let Some(err) = result.as_error() {
-            return Err(Error::Zint(err));
-        }
-        let svg = unsafe {
-            let memfile = std::slice::from_raw_parts(self.memfile, self.memfile_size as usize);
-            memfile
+    /// Encodes `data` into SVG.
+    ///
+    /// `data` can be any byte sequence, including binary data with NULs (see
+    /// [`InputMode::DATA`](crate::options::input_mode::InputMode::DATA)), or
+    /// a list of [`Segment`]s with different ECIs.
+    pub fn encode_svg<'a>(&mut self, data: impl Into<Input<'a>>) -> Result<Encoded<String>, Error> {
+        let Encoded {
+            output,
+            info,
+            warning,
+            message,
+        } = self.encode_to_memfile(data.into(), "svg")?;
+        let output =
+            String::from_utf8(output).map_err(|e| Error::InvalidResultSVG(e.utf8_error()))?;
+        Ok(Encoded {
+            output,
+            info,
+            warning,
+            message,
+        })
+    }
+
+    /// Encodes `data` into Encapsulated PostScript.
+    ///
+    /// Set [`CMYK_COLOR`](output_options::OutputOptions::CMYK_COLOR) output
+    /// option to produce CMYK output.
+    #[cfg(feature = "eps")]
+    pub fn encode_eps<'a>(
+        &mut self,
+        data: impl Into<Input<'a>>,
+    ) -> Result<Encoded<Vec<u8>>, Error> {
+        self.encode_to_memfile(data.into(), "eps")
+    }
+
+    /// Encodes `data` into Windows Enhanced Metafile.
+    #[cfg(feature = "emf")]
+    pub fn encode_emf<'a>(
+        &mut self,
+        data: impl Into<Input<'a>>,
+    ) -> Result<Encoded<Vec<u8>>, Error> {
+        self.encode_to_memfile(data.into(), "emf")
+    }
+
+    /// Encodes `data` into an image of provided raster `format`.
+    #[cfg(feature = "raster")]
+    pub fn encode_raster<'a>(
+        &mut self,
+        data: impl Into<Input<'a>>,
+        format: RasterFormat,
+    ) -> Result<Encoded<Vec<u8>>, Error> {
+        self.encode_to_memfile(data.into(), format.extension())
+    }
+
+    /// Encodes `data` into owned vector representation.
+    pub fn encode_vector<'a>(
+        &mut self,
+        data: impl Into<Input<'a>>,
+    ) -> Result<Encoded<Vector>, Error> {
+        self.encode_with(data.into(), Buffer::Vector, "svg", |symbol| {
+            let foreground =
+                Color::from_zint_string(&crate::util::cstr_to_string(&symbol.fgcolour))?;
+            let background =
+                Color::from_zint_string(&crate::util::cstr_to_string(&symbol.bgcolour))?;
+            let vector = unsafe {
+                // Safety: zint allocates vector on successful ZBarcode_Buffer_Vector
+                symbol
+                    .vector
+                    .as_ref()
+                    .expect("zint didn't produce a vector")
+            };
+            Ok(unsafe {
+                // Safety: vector was produced by zint and is valid until it's cleared
+                Vector::from_raw(vector, foreground, background)
+            })
+        })
+    }
+
+    /// Encodes `data` and returns its module grid.
+    pub fn encode_matrix<'a>(
+        &mut self,
+        data: impl Into<Input<'a>>,
+    ) -> Result<Encoded<ModuleMatrix>, Error> {
+        self.encode_with(data.into(), Buffer::Raster, "gif", |symbol| {
+            Ok(ModuleMatrix::from_symbol(symbol))
+        })
+    }
+
+    /// Encodes `data` and prints it into memory file.
+    ///
+    /// Output format is selected by Zint based on `extension` of the output
+    /// file name.
+    fn encode_to_memfile(
+        &mut self,
+        data: Input<'_>,
+        extension: &str,
+    ) -> Result<Encoded<Vec<u8>>, Error> {
+        crate::util::copy_into_cstr(format!("res.{}", extension), &mut self.outfile);
+
+        self.encode_with(data, Buffer::Print, extension, |symbol| {
+            let memfile = unsafe {
+                // Safety: zint allocates memfile_size bytes when BARCODE_MEMORY_FILE is set
+                std::slice::from_raw_parts(symbol.memfile, symbol.memfile_size as usize)
+            };
+            Ok(memfile.to_vec())
+        })
+    }
+
+    /// Encodes `data` and copies output out of the symbol with `read`.
+    ///
+    /// Outputs of any previous encoding are cleared (`ZBarcode_Clear`)
+    /// beforehand. Zint overwrites some of the inputs (e.g. `height` and
+    /// `option_1`/`option_2`) while encoding, so configuration is restored
+    /// afterwards to keep the symbol reusable.
+    ///
+    /// `filetype` is the extension of the output format, used to compute
+    /// `scale` from the X-dimension.
+    fn encode_with<T>(
+        &mut self,
+        data: Input<'_>,
+        buffer: Buffer,
+        filetype: &str,
+        read: impl FnOnce(&Self) -> Result<T, Error>,
+    ) -> Result<Encoded<T>, Error> {
+        // Rotating the bitmap doesn't affect the module grid
+        let rotate_angle = match buffer {
+            Buffer::Raster => 0,
+            Buffer::Print | Buffer::Vector => self.rotation.degrees(),
         };
-        let svg = String::from_utf8_lossy(svg).to_string();
+        let scale = self.scale_for(filetype)?;
+        let configured = unsafe {
+            // Safety: Symbol is always created as a valid zint_symbol; outputs
+            // are cleared first so the copy doesn't alias any allocations
+            ZBarcode_Clear(self.inner);
+            std::ptr::read(self.inner)
+        };
+        if let Some(scale) = scale {
+            self.scale = scale;
+        }
+
+        let result = self
+            .encode_raw(data, rotate_angle, buffer)
+            .and_then(|encoded| {
+                let output = read(self)?;
+                Ok(encoded.map(|_| output))
+            });
+
+        unsafe {
+            // Safety: outputs are freed before configuration (without
+            // outputs) is restored
+            ZBarcode_Clear(self.inner);
+            std::ptr::write(self.inner, configured);
+        }
+        result
+    }
+
+    /// Computes `scale` for output `filetype` from the configured X-dimension
+    /// and resolution (`ZBarcode_Scale_From_XdimDp`).
+    ///
+    /// Returns `None` if X-dimension isn't configured.
+    fn scale_for(&self, filetype: &str) -> Result<Option<f32>, Error> {
+        let Some(x_dimension) = self.x_dimension else {
+            return Ok(None);
+        };
+        let symbology = Symbology::try_from(self.symbology)?;
+        check_scaling(symbology, Some(x_dimension), Some(self.dpmm))?;
+        let filetype = CString::new(filetype).expect("filetype contains NUL");
+        let scale = unsafe {
+            ZBarcode_Scale_From_XdimDp(self.symbology, x_dimension, self.dpmm, filetype.as_ptr())
+        };
+        Ok(Some(scale))
+    }
+
+    /// Encodes `data` using one of `ZBarcode_Encode_and_*` or
+    /// `ZBarcode_Encode_Segs_and_*` functions, depending on `buffer`.
+    ///
+    /// Zint warnings and `errtxt` message are carried by the returned
+    /// [`Encoded`] value.
+    fn encode_raw(
+        &self,
+        data: Input<'_>,
+        rotate_angle: i32,
+        buffer: Buffer,
+    ) -> Result<Encoded<()>, Error> {
+        let result = match data {
+            Input::Data(data) => {
+                let encode: unsafe extern "C" fn(*mut zint_symbol, *const u8, i32, i32) -> i32 =
+                    match buffer {
+                        Buffer::Print => ZBarcode_Encode_and_Print,
+                        Buffer::Raster => ZBarcode_Encode_and_Buffer,
+                        Buffer::Vector => ZBarcode_Encode_and_Buffer_Vector,
+                    };
+                unsafe {
+                    encode(
+                        self.inner,
+                        source_ptr(data),
+                        source_length(data)?,
+                        rotate_angle,
+                    )
+                }
+            }
+            Input::Segments(segments) => {
+                let eci_supported =
+                    CapabilitySet::query(self.symbology).supports(CapabilityFlags::Eci);
+                if !eci_supported && segments.iter().any(|it| it.eci != 0) {
+                    return Err(Error::UnsupportedCapability(CapabilityFlags::Eci));
+                }
+
+                let segs = segments
+                    .iter()
+                    .map(|it| {
+                        Ok(zint_seg {
+                            // zint doesn't modify segment source
+                            source: source_ptr(it.data) as *mut u8,
+                            length: source_length(it.data)?,
+                            eci: it.eci,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let seg_count = i32::try_from(segs.len())
+                    .map_err(|_| Error::DataTooLong { size: segs.len() })?;
 
+                let encode: unsafe extern "C" fn(
+                    *mut zint_symbol,
+                    *const zint_seg,
+                    i32,
+                    i32,
+                ) -> i32 = match buffer {
+                    Buffer::Print => ZBarcode_Encode_Segs_and_Print,
+                    Buffer::Raster => ZBarcode_Encode_Segs_and_Buffer,
+                    Buffer::Vector => ZBarcode_Encode_Segs_and_Buffer_Vector,
+                };
+                unsafe { encode(self.inner, segs.as_ptr(), seg_count, rotate_angle) }
+            }
+        };
+
+        let result = ZintResult::from(result as u32);
+        let message = Some(crate::util::cstr_to_string(&self.errtxt)).filter(|it| !it.is_empty());
         match result.as_error() {
-            Some(err) => Err(Error::Zint(err)),
-            None => Ok(svg),
+            Some(kind) => Err(Error::Zint { kind, message }),
+            None => Ok(Encoded {
+                output: (),
+                info: EncodeInfo::from_symbol(self),
+                warning: result.as_warning(),
+                message,
+            }),
+        }
+    }
+}
+
+/// Kind of output produced while encoding.
+#[derive(Debug, Clone, Copy)]
+enum Buffer {
+    /// Print into (memory) file
+    Print,
+    /// Produce raster bitmap
+    Raster,
+    /// Produce vector representation
+    Vector,
+}
+
+/// Returns a pointer to `data` that Zint can read from.
+///
+/// Zint treats 0 length as NUL-terminated source, so empty data has to point
+/// to a valid C string.
+fn source_ptr(data: &[u8]) -> *const u8 {
+    if data.is_empty() {
+        c"".as_ptr() as *const u8
+    } else {
+        data.as_ptr()
+    }
+}
+
+/// Returns explicit `data` length passed to Zint.
+fn source_length(data: &[u8]) -> Result<i32, Error> {
+    i32::try_from(data.len()).map_err(|_| Error::DataTooLong { size: data.len() })
+}
+
+/// Data encoded by a [`Symbol`].
+///
+/// Usually created from `&str`, byte slices or slices of [`Segment`]s.
+#[derive(Debug, Clone, Copy)]
+pub enum Input<'a> {
+    /// Arbitrary bytes, encoded with [`Options::eci`]
+    Data(&'a [u8]),
+    /// Multiple segments, each with its own ECI
+    Segments(&'a [Segment<'a>]),
+}
+
+impl<'a> From<&'a [u8]> for Input<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Input::Data(value)
+    }
+}
+impl<'a, const N: usize> From<&'a [u8; N]> for Input<'a> {
+    fn from(value: &'a [u8; N]) -> Self {
+        Input::Data(value)
+    }
+}
+impl<'a> From<&'a Vec<u8>> for Input<'a> {
+    fn from(value: &'a Vec<u8>) -> Self {
+        Input::Data(value)
+    }
+}
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(value: &'a str) -> Self {
+        Input::Data(value.as_bytes())
+    }
+}
+impl<'a> From<&'a String> for Input<'a> {
+    fn from(value: &'a String) -> Self {
+        Input::Data(value.as_bytes())
+    }
+}
+impl<'a> From<&'a [Segment<'a>]> for Input<'a> {
+    fn from(value: &'a [Segment<'a>]) -> Self {
+        Input::Segments(value)
+    }
+}
+impl<'a, const N: usize> From<&'a [Segment<'a>; N]> for Input<'a> {
+    fn from(value: &'a [Segment<'a>; N]) -> Self {
+        Input::Segments(value)
+    }
+}
+impl<'a> From<&'a Vec<Segment<'a>>> for Input<'a> {
+    fn from(value: &'a Vec<Segment<'a>>) -> Self {
+        Input::Segments(value)
+    }
+}
+
+/// Segment of data with its own Extended Channel Interpretation.
+///
+/// Allows mixing character sets within a single symbol, e.g. Latin-2 text
+/// followed by Shift JIS text. Symbology must support
+/// [ECI](CapabilityFlags::Eci) for segments with non-zero `eci`.
+#[derive(Debug, Clone, Copy)]
+pub struct Segment<'a> {
+    /// Data to encode
+    pub data: &'a [u8],
+    /// Extended Channel Interpretation; `0` for none
+    pub eci: i32,
+}
+
+impl<'a> Segment<'a> {
+    pub fn new<D: AsRef<[u8]> + ?Sized>(data: &'a D, eci: i32) -> Self {
+        Segment {
+            data: data.as_ref(),
+            eci,
+        }
+    }
+}
+
+/// Successfully encoded output along with any warning reported by Zint.
+#[derive(Debug, Clone)]
+pub struct Encoded<T> {
+    /// Encoded output
+    pub output: T,
+    /// Properties of the encoded symbol
+    pub info: EncodeInfo,
+    /// Warning returned by Zint, if any
+    pub warning: Option<ZintWarning>,
+    /// Human readable message (`errtxt`) describing the warning
+    pub message: Option<String>,
+}
+
+impl<T> Encoded<T> {
+    /// Maps output while preserving warnings.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Encoded<U> {
+        Encoded {
+            output: f(self.output),
+            info: self.info,
+            warning: self.warning,
+            message: self.message,
+        }
+    }
+
+    /// Returns output, discarding any warnings.
+    pub fn into_output(self) -> T {
+        self.output
+    }
+}
+
+/// Properties of an encoded symbol that Zint determines while encoding.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EncodeInfo {
+    /// Number of rows in the symbol
+    pub rows: usize,
+    /// Width of the symbol in modules
+    pub width: usize,
+    /// Height of the symbol in X-dimensions, excluding text and whitespace
+    pub height: f32,
+    /// `option_1` used by Zint, e.g. selected error correction level
+    pub option_1: i32,
+    /// `option_2` used by Zint, e.g. selected version or size
+    pub option_2: i32,
+    /// Human Readable Text, as printed below the symbol
+    pub text: String,
+}
+
+impl EncodeInfo {
+    /// Copies properties out of an encoded `symbol`.
+    fn from_symbol(symbol: &zint_symbol) -> Self {
+        let text_len = symbol
+            .text
+            .iter()
+            .position(|it| *it == 0)
+            .unwrap_or(symbol.text.len());
+        EncodeInfo {
+            rows: symbol.rows.max(0) as usize,
+            width: symbol.width.max(0) as usize,
+            height: symbol.height,
+            option_1: symbol.option_1,
+            option_2: symbol.option_2,
+            text: String::from_utf8_lossy(&symbol.text[..text_len]).to_string(),
+        }
+    }
+}
+
+/// Raster image formats
+#[cfg(feature = "raster")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RasterFormat {
+    /// Portable Network Graphics
+    #[cfg(feature = "png")]
+    Png,
+    /// Windows Bitmap
+    #[cfg(feature = "bmp")]
+    Bmp,
+    /// Graphics Interchange Format
+    #[cfg(feature = "gif")]
+    Gif,
+    /// ZSoft Paintbrush
+    #[cfg(feature = "pcx")]
+    Pcx,
+    /// Tagged Image File Format
+    #[cfg(feature = "tif")]
+    Tif,
+}
+
+#[cfg(feature = "raster")]
+impl RasterFormat {
+    /// File extension Zint uses to select the output format.
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "png")]
+            RasterFormat::Png => "png",
+            #[cfg(feature = "bmp")]
+            RasterFormat::Bmp => "bmp",
+            #[cfg(feature = "gif")]
+            RasterFormat::Gif => "gif",
+            #[cfg(feature = "pcx")]
+            RasterFormat::Pcx => "pcx",
+            #[cfg(feature = "tif")]
+            RasterFormat::Tif => "tif",
         }
     }
 }
@@ -147,6 +653,8 @@ impl Default for Symbol {
     fn default() -> Self {
         Self {
             inner: unsafe { zint_wasm_sys::ZBarcode_Create() },
+            x_dimension: None,
+            rotation: Rotation::default(),
         }
     }
 }
@@ -0,0 +1,234 @@
+use zint_wasm_sys::{
+    zint_vector, zint_vector_circle, zint_vector_hexagon, zint_vector_rect, zint_vector_string,
+};
+
+use crate::options::color::Color;
+
+/// Color of a vector element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorColor {
+    /// Drawn with foreground color
+    Foreground,
+    /// Drawn with background color
+    Background,
+    Cyan,
+    Blue,
+    Magenta,
+    Red,
+    Yellow,
+    Green,
+    Black,
+    White,
+}
+
+impl VectorColor {
+    /// Converts `colour` value of a rectangle.
+    ///
+    /// Zint uses `-1` for foreground and `1`-`8` for Ultracode colors.
+    pub(crate) fn from_rect_colour(colour: i32) -> Self {
+        match colour {
+            1 => VectorColor::Cyan,
+            2 => VectorColor::Blue,
+            3 => VectorColor::Magenta,
+            4 => VectorColor::Red,
+            5 => VectorColor::Yellow,
+            6 => VectorColor::Green,
+            7 => VectorColor::Black,
+            8 => VectorColor::White,
+            0 => VectorColor::Background,
+            _ => VectorColor::Foreground,
+        }
+    }
+
+    /// Converts `colour` value of a circle.
+    ///
+    /// Zint uses `0` for foreground and anything else for background.
+    fn from_circle_colour(colour: i32) -> Self {
+        if colour == 0 {
+            VectorColor::Foreground
+        } else {
+            VectorColor::Background
+        }
+    }
+}
+
+/// Horizontal alignment of a text element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    Center,
+    Left,
+    Right,
+}
+
+impl From<i32> for TextAlign {
+    fn from(halign: i32) -> Self {
+        match halign {
+            1 => TextAlign::Left,
+            2 => TextAlign::Right,
+            _ => TextAlign::Center,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rectangle {
+    /// Left edge
+    pub x: f32,
+    /// Top edge
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub color: VectorColor,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hexagon {
+    /// Horizontal center
+    pub x: f32,
+    /// Vertical center
+    pub y: f32,
+    /// Diameter of the inscribed circle
+    pub diameter: f32,
+    /// Rotation in degrees (0, 90, 180 or 270); 0 has apex at the top
+    pub rotation: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle {
+    /// Horizontal center
+    pub x: f32,
+    /// Vertical center
+    pub y: f32,
+    /// Diameter, not including `width`
+    pub diameter: f32,
+    /// Width of circle perimeter; `0` for a filled disc
+    pub width: f32,
+    pub color: VectorColor,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    /// Horizontal position, relative to `align`
+    pub x: f32,
+    /// Vertical position of the baseline
+    pub y: f32,
+    pub font_size: f32,
+    /// Estimate of rendered width
+    pub width: f32,
+    /// Rotation in degrees (0, 90, 180 or 270)
+    pub rotation: i32,
+    pub align: TextAlign,
+    pub text: String,
+}
+
+/// Owned copy of the vector representation of a symbol produced by
+/// `ZBarcode_Buffer_Vector`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vector {
+    /// Width of the image, including text and whitespace
+    pub width: f32,
+    /// Height of the image, including text and whitespace
+    pub height: f32,
+    pub foreground: Color,
+    pub background: Color,
+    pub rectangles: Vec<Rectangle>,
+    pub hexagons: Vec<Hexagon>,
+    pub circles: Vec<Circle>,
+    pub strings: Vec<Text>,
+}
+
+/// Maps every element of a Zint linked list.
+///
+/// # Safety
+///
+/// `first` must be null or point to a valid list whose `next` pointers are
+/// valid or null.
+unsafe fn collect_list<T, R>(
+    first: *mut T,
+    next: impl Fn(&T) -> *mut T,
+    map: impl Fn(&T) -> R,
+) -> Vec<R> {
+    let mut result = Vec::new();
+    let mut current = first;
+    while let Some(item) = current.as_ref() {
+        result.push(map(item));
+        current = next(item);
+    }
+    result
+}
+
+impl Vector {
+    /// # Safety
+    ///
+    /// `vector` must be a valid vector produced by Zint.
+    pub(crate) unsafe fn from_raw(
+        vector: &zint_vector,
+        foreground: Color,
+        background: Color,
+    ) -> Self {
+        let rectangles = collect_list(
+            vector.rectangles,
+            |it: &zint_vector_rect| it.next,
+            |it| Rectangle {
+                x: it.x,
+                y: it.y,
+                width: it.width,
+                height: it.height,
+                color: VectorColor::from_rect_colour(it.colour),
+            },
+        );
+        let hexagons = collect_list(
+            vector.hexagons,
+            |it: &zint_vector_hexagon| it.next,
+            |it| Hexagon {
+                x: it.x,
+                y: it.y,
+                diameter: it.diameter,
+                rotation: it.rotation,
+            },
+        );
+        let circles = collect_list(
+            vector.circles,
+            |it: &zint_vector_circle| it.next,
+            |it| Circle {
+                x: it.x,
+                y: it.y,
+                diameter: it.diameter,
+                width: it.width,
+                color: VectorColor::from_circle_colour(it.colour),
+            },
+        );
+        let strings = collect_list(
+            vector.strings,
+            |it: &zint_vector_string| it.next,
+            |it| {
+                let text = if it.text.is_null() {
+                    &[][..]
+                } else {
+                    // Safety: zint stores `length` bytes of text
+                    std::slice::from_raw_parts(it.text, it.length.max(0) as usize)
+                };
+                Text {
+                    x: it.x,
+                    y: it.y,
+                    font_size: it.fsize,
+                    width: it.width,
+                    rotation: it.rotation,
+                    align: TextAlign::from(it.halign),
+                    text: String::from_utf8_lossy(text).to_string(),
+                }
+            },
+        );
+
+        Vector {
+            width: vector.width,
+            height: vector.height,
+            foreground,
+            background,
+            rectangles,
+            hexagons,
+            circles,
+            strings,
+        }
+    }
+}
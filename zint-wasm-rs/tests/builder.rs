@@ -0,0 +1,50 @@
+use zint_wasm_rs::{
+    error::Error,
+    options::{
+        symbology::Symbology,
+        symbology_options::{QrEcc, QrOptions, SymbologyOptions},
+        Options,
+    },
+};
+
+#[test]
+fn unsupported_ecc() {
+    for (symbology, ecc) in [
+        (Symbology::RMQR, QrEcc::L),
+        (Symbology::RMQR, QrEcc::Q),
+        (Symbology::MicroQR, QrEcc::H),
+    ] {
+        let error = Options::builder(symbology).ecc(ecc).build().unwrap_err();
+        assert!(
+            matches!(error, Error::UnsupportedEcc { ecc: it, symbology: s } if it == ecc && s == symbology),
+            "{:?}",
+            error
+        );
+
+        let options = SymbologyOptions::from(QrOptions {
+            ecc: Some(ecc),
+            version: None,
+        });
+        assert!(matches!(
+            options.check(symbology),
+            Err(Error::UnsupportedEcc { .. })
+        ));
+    }
+    assert_eq!(
+        Options::builder(Symbology::RMQR)
+            .ecc(QrEcc::Q)
+            .build()
+            .unwrap_err()
+            .to_string(),
+        "ecc level Q isn't supported by RMQR"
+    );
+
+    for ecc in [QrEcc::M, QrEcc::H] {
+        let options = Options::builder(Symbology::RMQR).ecc(ecc).build().unwrap();
+        assert_eq!(options.option_1, Some(ecc as i32));
+    }
+    assert!(matches!(
+        Options::builder(Symbology::Code128).ecc(QrEcc::M).build(),
+        Err(Error::IncompatibleOption { which: "ecc", .. })
+    ));
+}
@@ -0,0 +1,101 @@
+use zint_wasm_rs::{check_digit::Scheme, error::Error};
+
+/// Published numbers with their check digits
+const KNOWN_ANSWERS: &[(Scheme, &str, &str)] = &[
+    (Scheme::Ean8, "9638507", "96385074"),
+    (Scheme::Ean13, "400638133393", "4006381333931"),
+    (Scheme::UpcA, "03600029145", "036000291452"),
+    (Scheme::UpcE, "0425261", "04252614"),
+    (Scheme::Isbn10, "030640615", "0306406152"),
+    (Scheme::Isbn10, "080442957", "080442957X"),
+    (Scheme::Isbn13, "978030640615", "9780306406157"),
+    (Scheme::Itf14, "1540014128876", "15400141288763"),
+    (Scheme::Ean14, "1540014128876", "15400141288763"),
+    (Scheme::Sscc, "10614141123456789", "106141411234567897"),
+    (Scheme::Pzn, "0275883", "02758830"),
+    (Scheme::Vin, "1M8GDM9AKP042788", "1M8GDM9AXKP042788"),
+    (Scheme::Code39Mod43, "CODE39", "CODE39W"),
+    (Scheme::MsiMod10, "1234567", "12345674"),
+    (Scheme::MsiMod11, "1234567", "12345674"),
+    (Scheme::Code11, "123-45", "123-4552"),
+];
+
+#[test]
+fn known_answers() {
+    for (scheme, data, with_check) in KNOWN_ANSWERS {
+        assert_eq!(
+            scheme.append(data).unwrap(),
+            *with_check,
+            "{} of {}",
+            scheme,
+            data
+        );
+        assert!(scheme.is_valid(with_check), "{} of {}", scheme, with_check);
+    }
+    // every scheme is covered
+    for scheme in Scheme::ALL {
+        assert!(KNOWN_ANSWERS.iter().any(|(it, _, _)| it == scheme));
+    }
+}
+
+#[test]
+fn upce_uses_upca_check_digit() {
+    // UPC-E 0425261 is UPC-A 04210000526
+    assert_eq!(Scheme::UpcE.compute("0425261").unwrap(), "4");
+    assert!(Scheme::UpcA.is_valid("042100005264"));
+    assert_eq!(Scheme::UpcE.append("0123450").unwrap(), "01234505");
+    assert!(Scheme::UpcA.is_valid("012000003455"));
+    assert!(matches!(
+        Scheme::UpcE.compute("2425261"),
+        Err(Error::InvalidCheckDigitData { .. })
+    ));
+}
+
+#[test]
+fn lowercase_x() {
+    assert!(Scheme::Isbn10.is_valid("080442957x"));
+    assert!(Scheme::Vin.is_valid("1M8GDM9AxKP042788"));
+    assert!(!Scheme::Code39Mod43.is_valid("CODE39w"));
+}
+
+#[test]
+fn mismatches() {
+    let error = Scheme::Ean13.verify("4006381333932").unwrap_err();
+    assert_eq!(error.to_string(), "EAN-13 check digit should be 1; got 2");
+    let error = Scheme::Isbn10.verify("0306406153").unwrap_err();
+    assert_eq!(error.to_string(), "ISBN-10 check digit should be 2; got 3");
+    let error = Scheme::Code11.verify("123-4525").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Code 11 check digit should be 52; got 25"
+    );
+}
+
+#[test]
+fn invalid_data() {
+    let reason = |result: Result<String, Error>| match result {
+        Err(Error::InvalidCheckDigitData { reason, .. }) => reason,
+        other => panic!("expected InvalidCheckDigitData error; got {:?}", other),
+    };
+    assert_eq!(
+        reason(Scheme::Ean13.compute("40063813339")),
+        "must have 12 characters without check digit; got 11"
+    );
+    assert_eq!(
+        reason(Scheme::Isbn13.compute("977030640615")),
+        "must start with 978 or 979"
+    );
+    assert_eq!(
+        reason(Scheme::Vin.compute("1M8GDM9IKP042788")),
+        "character 'I' isn't allowed"
+    );
+    assert_eq!(
+        reason(Scheme::Code39Mod43.compute("code39")),
+        "character 'c' isn't allowed"
+    );
+    assert_eq!(reason(Scheme::MsiMod10.compute("")), "data is empty");
+    assert!(matches!(
+        Scheme::Ean8.verify("9638507"),
+        Err(Error::InvalidCheckDigitData { .. })
+    ));
+}
@@ -0,0 +1,157 @@
+use zint_wasm_rs::{
+    error::Error,
+    options::{
+        color::Color,
+        input_mode::InputMode,
+        option3::{DataMatrixOption, Option3, QRMask, QRMatrixOption},
+        output_options::OutputOptions,
+        symbology::Symbology,
+        symbology_options::{QrEcc, QrOptions},
+        Options,
+    },
+};
+
+/// Parses `command_line`, then parses its `to_cli_args` again and checks both
+/// produce the same arguments.
+fn round_trip(command_line: &str) -> (Options, Vec<String>) {
+    let options = Options::from_cli(command_line).unwrap();
+    let args = options.to_cli_args();
+    let parsed = Options::from_cli_args(&args).unwrap();
+    assert_eq!(parsed.to_cli_args(), args, "{}", command_line);
+    (parsed, args)
+}
+
+#[test]
+fn parse_example() {
+    let options = Options::from_cli(
+        "zint --barcode=58 --scale=2 --secure=4 --vers=10 --eci=26 --gs1 --dotty",
+    )
+    .unwrap();
+    assert_eq!(options.symbology, Symbology::QRCode);
+    assert_eq!(options.scale, Some(2.0));
+    assert_eq!(options.option_1, Some(4));
+    assert_eq!(options.option_2, Some(10));
+    assert_eq!(options.eci, Some(26));
+    assert_eq!(
+        options.input_mode.map(|it| it.bits()),
+        Some(InputMode::GS1.bits())
+    );
+    assert_eq!(
+        options.output_options.map(|it| it.bits()),
+        Some(OutputOptions::BARCODE_DOTTY_MODE.bits())
+    );
+
+    let (_, args) =
+        round_trip("zint --barcode=58 --scale=2 --secure=4 --vers=10 --eci=26 --gs1 --dotty");
+    for arg in [
+        "--barcode=58",
+        "--scale=2",
+        "--secure=4",
+        "--vers=10",
+        "--eci=26",
+        "--gs1",
+        "--dotty",
+    ] {
+        assert!(
+            args.iter().any(|it| it == arg),
+            "{} missing in {:?}",
+            arg,
+            args
+        );
+    }
+}
+
+#[test]
+fn mask_round_trip() {
+    let (options, args) = round_trip("--barcode=58 --mask=3 --fullmultibyte");
+    assert_eq!(
+        options.option_3,
+        Some(Option3::QRMatrix(
+            QRMatrixOption::from(QRMask::Mask3) | QRMatrixOption::FULL_MULITIBYTE
+        ))
+    );
+    assert!(args.iter().any(|it| it == "--mask=3"));
+    assert!(args.iter().any(|it| it == "--fullmultibyte"));
+}
+
+#[test]
+fn scmvv_round_trip() {
+    let (options, args) = round_trip("--barcode=57 --primary=152382802840001 --mode=2 --scmvv=96");
+    assert_eq!(options.option_1, Some(2));
+    assert_eq!(options.option_2, Some(97));
+    assert_eq!(options.primary.as_deref(), Some("152382802840001"));
+    assert!(args.iter().any(|it| it == "--scmvv=96"));
+}
+
+#[test]
+fn reverse_and_binary_round_trip() {
+    let (options, args) = round_trip("-b 20 --reverse --binary");
+    assert_eq!(
+        options.fg_color,
+        Some(Color::Rgba {
+            r: u8::MAX,
+            g: u8::MAX,
+            b: u8::MAX,
+            a: u8::MAX,
+        })
+    );
+    assert_eq!(options.bg_color, Some(Color::BLACK));
+    assert_eq!(
+        options.input_mode.map(|it| it.bits()),
+        Some(InputMode::DATA.bits())
+    );
+    assert!(args.iter().any(|it| it == "--binary"));
+}
+
+#[test]
+fn other_round_trips() {
+    for command_line in [
+        "--barcode=74 --rows=10 --cols=12",
+        "--barcode=71 --square --esc --gs1parens --gs1",
+        "--barcode=55 --cols=5 --secure=3 --rotate=270",
+        "--barcode=20 --fg=10,20,30,40 --bg=ff000080 --notext --structapp=1,2,id",
+        "--barcode=58 --scalexdimdp=0.5mm,600dpi",
+    ] {
+        round_trip(command_line);
+    }
+}
+
+#[test]
+fn options_round_trip() {
+    let options = Options::builder(Symbology::QRCode)
+        .symbology_options(QrOptions {
+            ecc: Some(QrEcc::Q),
+            version: Some(7),
+        })
+        .mask(QRMask::Mask5)
+        .input_mode(InputMode::DATA)
+        .build()
+        .unwrap();
+    let args = options.to_cli_args();
+    let parsed = Options::from_cli_args(&args).unwrap();
+    assert_eq!(parsed.option_1, Some(QrEcc::Q as i32));
+    assert_eq!(parsed.option_2, Some(7));
+    assert_eq!(parsed.option_3, options.option_3);
+    assert_eq!(parsed.to_cli_args(), args);
+
+    let options = Options::builder(Symbology::DataMatrix)
+        .data_matrix(DataMatrixOption::DMRE)
+        .input_mode(InputMode::UNICODE)
+        .build()
+        .unwrap();
+    let parsed = Options::from_cli_args(options.to_cli_args()).unwrap();
+    assert_eq!(parsed.option_3, options.option_3);
+}
+
+#[test]
+fn rows_depend_on_symbology() {
+    let options = Options::from_cli("--rows=10 --barcode=74").unwrap();
+    assert_eq!(options.option_1, Some(10));
+
+    for command_line in ["--barcode=55 --rows=10", "--barcode=84 --rows=10"] {
+        assert!(matches!(
+            Options::from_cli(command_line),
+            Err(Error::InvalidCliArgument { .. })
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+use std::str::FromStr;
+
+use zint_wasm_rs::{
+    error::Error,
+    gs1::{digital_link::DigitalLink, Date, Element, ElementString},
+    options::symbology::Symbology,
+};
+
+fn digital_link(elements: Vec<Element>) -> DigitalLink {
+    let mut result = ElementString::new();
+    for element in elements {
+        result.push(element).unwrap();
+    }
+    DigitalLink::new(result).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let uri = "https://id.gs1.org/01/09501101530003/10/ABC?17=250101";
+    let link = DigitalLink::from_str(uri).unwrap();
+    assert_eq!(link.domain(), DigitalLink::DEFAULT_DOMAIN);
+    assert_eq!(
+        link.elements().elements(),
+        &[
+            Element::Gtin("09501101530003".to_string()),
+            Element::Batch("ABC".to_string()),
+            Element::Expiry(Date::new(2025, 1, 1).unwrap()),
+        ]
+    );
+    assert_eq!(
+        link.primary_key(),
+        Some(&Element::Gtin("09501101530003".to_string()))
+    );
+    assert_eq!(link.to_string(), uri);
+    assert_eq!(
+        ElementString::from(link).to_string(),
+        "[01]09501101530003[10]ABC[17]250101"
+    );
+
+    let link =
+        DigitalLink::from_str("http://example.com/products/gtin/9501101530003/lot/ABC").unwrap();
+    assert_eq!(link.domain(), "http://example.com/products");
+    assert_eq!(
+        link.to_string(),
+        "http://example.com/products/01/09501101530003/10/ABC"
+    );
+}
+
+#[test]
+fn qualifier_order() {
+    let link = digital_link(vec![
+        Element::Serial("12345".to_string()),
+        Element::Expiry(Date::new(2025, 1, 1).unwrap()),
+        Element::Batch("ABC".to_string()),
+        Element::parse("22", "2A").unwrap(),
+        Element::gtin("9501101530003").unwrap(),
+    ]);
+    let uri = "https://id.gs1.org/01/09501101530003/22/2A/10/ABC/21/12345?17=250101";
+    assert_eq!(link.to_string(), uri);
+    assert_eq!(DigitalLink::from_str(uri).unwrap().to_string(), uri);
+
+    // qualifiers may be skipped but not reordered
+    let uri = "https://id.gs1.org/01/09501101530003/21/12345";
+    assert_eq!(DigitalLink::from_str(uri).unwrap().to_string(), uri);
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/01/09501101530003/21/12345/10/ABC")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; path must be primary key followed by key qualifiers in order"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/01/09501101530003/17/250101")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; path must be primary key followed by key qualifiers in order"
+    );
+}
+
+#[test]
+fn percent_encoding() {
+    let link = digital_link(vec![
+        Element::gtin("9501101530003").unwrap(),
+        Element::Batch("A/B%C&D?E+F".to_string()),
+        Element::Serial("x=1;2".to_string()),
+    ]);
+    let uri = "https://id.gs1.org/01/09501101530003/10/A%2FB%25C%26D%3FE%2BF/21/x%3D1%3B2";
+    assert_eq!(link.to_string(), uri);
+    assert_eq!(DigitalLink::from_str(uri).unwrap(), link);
+
+    let link = digital_link(vec![
+        Element::gtin("9501101530003").unwrap(),
+        Element::Other {
+            ai: "99".to_string(),
+            value: "A/B&C=D".to_string(),
+        },
+    ]);
+    let uri = "https://id.gs1.org/01/09501101530003?99=A%2FB%26C%3DD";
+    assert_eq!(link.to_string(), uri);
+    assert_eq!(DigitalLink::from_str(uri).unwrap(), link);
+
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/01/09501101530003/10/AB%2")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; incomplete percent-encoding"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/01/09501101530003/10/AB%ZZ")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; invalid percent-encoding"
+    );
+}
+
+#[test]
+fn invalid_uris() {
+    assert_eq!(
+        DigitalLink::from_str("ftp://id.gs1.org/01/09501101530003")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; URI must start with http:// or https://"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/10/ABC")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; missing primary key"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; missing primary key"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/01")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; path must be primary key followed by key qualifiers in order"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/01/09501101530003/10")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; path must be primary key followed by key qualifiers in order"
+    );
+    assert!(matches!(
+        DigitalLink::from_str("https://id.gs1.org/01/09501101530004"),
+        Err(Error::InvalidGs1 { .. })
+    ));
+    assert!(matches!(
+        DigitalLink::new(ElementString::new()),
+        Err(Error::InvalidDigitalLink {
+            reason: "missing primary key"
+        })
+    ));
+    assert!(matches!(
+        digital_link(vec![Element::gtin("9501101530003").unwrap()]).with_domain("id.gs1.org"),
+        Err(Error::InvalidDigitalLink { .. })
+    ));
+    assert!(matches!(
+        digital_link(vec![Element::gtin("9501101530003").unwrap()]).options(Symbology::Code128),
+        Err(Error::IncompatibleOption { .. })
+    ));
+}
+
+#[test]
+fn compressed() {
+    let link = digital_link(vec![Element::gtin("5412345000013").unwrap()]);
+    let uri = "https://id.gs1.org/AQnYUc1gmg";
+    assert_eq!(link.to_compressed_string().unwrap(), uri);
+    assert_eq!(DigitalLink::from_str(uri).unwrap(), link);
+
+    // every encoding of alphanumeric values along with variable length and
+    // multi-component numeric values
+    for value in ["12345", "abc123", "ABC123", "Ab-_9", "A/B%C&D"] {
+        let link = digital_link(vec![
+            Element::gtin("9501101530003").unwrap(),
+            Element::Batch(value.to_string()),
+            Element::Count(42),
+            Element::Expiry(Date::new(2025, 1, 1).unwrap()),
+            Element::parse("253", "9501101530009ABC").unwrap(),
+        ])
+        .with_domain("https://example.com/products")
+        .unwrap();
+        let uri = link.to_compressed_string().unwrap();
+        assert!(uri.starts_with("https://example.com/products/"));
+        let parsed = DigitalLink::from_str(&uri).unwrap();
+        assert_eq!(parsed, link);
+        assert_eq!(parsed.to_string(), link.to_string());
+    }
+
+    let link = digital_link(vec![
+        Element::gtin("9501101530003").unwrap(),
+        Element::Other {
+            ai: "99".to_string(),
+            value: "ABC".to_string(),
+        },
+    ]);
+    assert_eq!(
+        link.to_compressed_string().unwrap_err().to_string(),
+        "invalid GS1 Digital Link; AI isn't supported in compressed URIs"
+    );
+}
+
+#[test]
+fn invalid_compressed_uris() {
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/AQnYUc1g")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; compressed data is truncated"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/BQA")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; unknown AI in compressed URI"
+    );
+    assert_eq!(
+        DigitalLink::from_str("https://id.gs1.org/_wA")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 Digital Link; optimized compressed URIs aren't supported"
+    );
+    // GTIN with wrong check digit
+    assert!(matches!(
+        DigitalLink::from_str("https://id.gs1.org/AQnYUc1gnA"),
+        Err(Error::InvalidGs1 { .. })
+    ));
+}
@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use zint_wasm_rs::{
+    gs1::{AiFormat, Date, Decimal, Element, ElementString},
+    options::{input_mode::InputMode, symbology::Symbology},
+};
+
+#[test]
+fn dates() {
+    assert_eq!(Date::new(2025, 1, 31).unwrap().to_string(), "250131");
+    assert_eq!(Date::new(2024, 2, 29).unwrap().to_string(), "240229");
+    assert_eq!(Date::new(2025, 6, 0).unwrap().day(), 0);
+    assert_eq!(
+        Date::from_str("991231").unwrap(),
+        Date::new(1999, 12, 31).unwrap()
+    );
+
+    assert_eq!(
+        Date::new(2025, 2, 29).unwrap_err().to_string(),
+        "invalid GS1 data; day must be between 00 and 28 for month 02; got 29"
+    );
+    assert_eq!(
+        Date::new(2025, 4, 31).unwrap_err().to_string(),
+        "invalid GS1 data; day must be between 00 and 30 for month 04; got 31"
+    );
+    assert_eq!(
+        Date::new(2025, 13, 1).unwrap_err().to_string(),
+        "invalid GS1 data; month must be between 01 and 12; got 13"
+    );
+    assert_eq!(
+        Date::new(2025, 0, 1).unwrap_err().to_string(),
+        "invalid GS1 data; month must be between 01 and 12; got 00"
+    );
+    assert_eq!(
+        Date::from_str("2501").unwrap_err().to_string(),
+        "invalid GS1 data; date must be in YYMMDD format; got \"2501\""
+    );
+
+    assert_eq!(
+        Element::parse("17", "250230").unwrap_err().to_string(),
+        "invalid GS1 data; (17) day must be between 00 and 28 for month 02; got 30"
+    );
+}
+
+#[test]
+fn decimals() {
+    let decimal = Decimal::from_str("1.500").unwrap();
+    assert_eq!((decimal.value(), decimal.decimals()), (1500, 3));
+    assert_eq!(decimal.to_string(), "1.500");
+    assert_eq!(decimal.as_f64(), 1.5);
+    assert_eq!(Decimal::from_str("42").unwrap().to_string(), "42");
+    assert_eq!(Decimal::new(5, 2).unwrap().to_string(), "0.05");
+
+    let element = Element::NetWeightKg(decimal);
+    assert_eq!(
+        (element.ai(), element.value()),
+        ("3103".to_string(), "001500".to_string())
+    );
+    assert_eq!(Element::parse("3103", "001500").unwrap(), element);
+    assert_eq!(
+        Element::parse("3202", "012345").unwrap(),
+        Element::NetWeightLb(Decimal::new(12345, 2).unwrap())
+    );
+
+    assert_eq!(
+        Decimal::new(1_000_000, 0).unwrap_err().to_string(),
+        "invalid GS1 data; measurement must have at most 6 digits; got 1000000"
+    );
+    assert_eq!(
+        Decimal::from_str("0.000001").unwrap_err().to_string(),
+        "invalid GS1 data; measurement must have at most 5 decimal places; got 6"
+    );
+    assert_eq!(
+        Decimal::from_str("1,5").unwrap_err().to_string(),
+        "invalid GS1 data; invalid measurement \"1,5\""
+    );
+    assert_eq!(
+        Element::parse("3106", "000100").unwrap_err().to_string(),
+        "invalid GS1 data; (3106) measurement must have at most 5 decimal places; got 6"
+    );
+    assert_eq!(
+        Element::parse("3103", "1500").unwrap_err().to_string(),
+        "invalid GS1 data; (3103) must have 6 digits; got \"1500\""
+    );
+}
+
+#[test]
+fn check_digits() {
+    assert_eq!(
+        Element::gtin("9501101530003").unwrap(),
+        Element::Gtin("09501101530003".to_string())
+    );
+    assert_eq!(Element::gtin("96385074").unwrap().value(), "00000096385074");
+    assert!(Element::parse("00", "106141411234567897").is_ok());
+    assert!(Element::parse("414", "9501101000001").is_ok());
+
+    assert_eq!(
+        Element::parse("01", "09501101530004")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 data; (01) check digit should be 3; got 4"
+    );
+    assert_eq!(
+        Element::parse("00", "106141411234567890")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 data; (00) check digit should be 7; got 0"
+    );
+    assert_eq!(
+        Element::gtin("950110153000").unwrap_err().to_string(),
+        "invalid GS1 data; (01) check digit should be 7; got 0"
+    );
+    assert_eq!(
+        Element::gtin("95011015300").unwrap_err().to_string(),
+        "invalid GS1 data; (01) GTIN must have 8, 12, 13 or 14 digits; got 11"
+    );
+}
+
+#[test]
+fn data_fields() {
+    assert_eq!(
+        Element::parse("10", "ABC#").unwrap_err().to_string(),
+        "invalid GS1 data; (10) character '#' isn't allowed"
+    );
+    assert_eq!(
+        Element::parse("21", &"1".repeat(21))
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 data; (21) must have 1 to 20 characters; got 21"
+    );
+    assert_eq!(
+        Element::parse("30", "123456789").unwrap_err().to_string(),
+        "invalid GS1 data; (30) must have 1 to 8 digits; got \"123456789\""
+    );
+    assert_eq!(
+        Element::parse("9", "x").unwrap_err().to_string(),
+        "invalid GS1 data; AI must have 2 to 4 digits; got \"9\""
+    );
+    assert_eq!(
+        Element::parse("99", "internal").unwrap(),
+        Element::Other {
+            ai: "99".to_string(),
+            value: "internal".to_string()
+        }
+    );
+    assert_eq!(
+        Element::Other {
+            ai: "01".to_string(),
+            value: "09501101530003".to_string()
+        }
+        .validate()
+        .unwrap_err()
+        .to_string(),
+        "invalid GS1 data; (01) has a typed element which should be used instead"
+    );
+}
+
+#[test]
+fn element_strings() {
+    let elements = ElementString::new()
+        .with(Element::gtin("9501101530003").unwrap())
+        .unwrap()
+        .with(Element::Expiry(Date::new(2025, 1, 1).unwrap()))
+        .unwrap()
+        .with(Element::Batch("AB(12)".to_string()))
+        .unwrap();
+    assert_eq!(
+        elements.to_string(),
+        "[01]09501101530003[17]250101[10]AB(12)"
+    );
+    let parentheses = elements.format(AiFormat::Parentheses);
+    assert_eq!(parentheses, r"(01)09501101530003(17)250101(10)AB\(12\)");
+    assert_eq!(
+        elements.input_mode(AiFormat::Parentheses).bits(),
+        (InputMode::GS1 | InputMode::GS1_PARENTHESES | InputMode::ESCAPE).bits()
+    );
+    assert_eq!(
+        elements.input_mode(AiFormat::Brackets).bits(),
+        InputMode::GS1.bits()
+    );
+
+    assert_eq!(
+        ElementString::from_str(&elements.to_string()).unwrap(),
+        elements
+    );
+    assert_eq!(ElementString::from_str(&parentheses).unwrap(), elements);
+    assert_eq!(
+        elements.get("17"),
+        Some(&Element::Expiry(Date::new(2025, 1, 1).unwrap()))
+    );
+
+    assert_eq!(
+        ElementString::from_str("[01]09501101530003[01]09501101530003")
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 data; (01) is present more than once"
+    );
+    assert_eq!(
+        ElementString::from_str("01").unwrap_err().to_string(),
+        "invalid GS1 data; element string must start with [ or ("
+    );
+    assert_eq!(
+        ElementString::from_str(r"(10)A\B").unwrap_err().to_string(),
+        "invalid GS1 data; invalid escape sequence"
+    );
+    assert_eq!(
+        ElementString::new()
+            .options(Symbology::GS1128, AiFormat::Brackets)
+            .unwrap_err()
+            .to_string(),
+        "invalid GS1 data; element string is empty"
+    );
+}
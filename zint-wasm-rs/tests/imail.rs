@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use zint_wasm_rs::{imail::IntelligentMail, options::symbology::Symbology};
+
+#[test]
+fn output_lengths() {
+    let imail = IntelligentMail::new(1, 234, "567094", "987654321").unwrap();
+    for (routing_code, expected) in [
+        ("", "01234567094987654321"),
+        ("01234", "0123456709498765432101234"),
+        ("012345678", "01234567094987654321012345678"),
+        ("01234567891", "0123456709498765432101234567891"),
+    ] {
+        let imail = imail.clone().with_routing_code(routing_code).unwrap();
+        assert_eq!(imail.routing_code(), routing_code);
+        assert_eq!(imail.to_string(), expected);
+        assert_eq!(imail.to_string().len(), 20 + routing_code.len());
+    }
+
+    assert_eq!(
+        imail.with_routing_code("0123456").unwrap_err().to_string(),
+        "invalid Intelligent Mail barcode; routing code must have 0, 5, 9 or 11 digits; got \"0123456\""
+    );
+    assert_eq!(
+        IntelligentMail::new(0, 0, "000000", "000000000")
+            .unwrap()
+            .to_string(),
+        "00000000000000000000"
+    );
+    assert_eq!(
+        IntelligentMail::new(1, 234, "567094", "987654321")
+            .unwrap()
+            .options()
+            .unwrap()
+            .symbology,
+        Symbology::USPSIMail
+    );
+}
+
+#[test]
+fn barcode_id() {
+    for barcode_id in [0, 4, 10, 94] {
+        assert!(IntelligentMail::new(barcode_id, 234, "567094", "987654321").is_ok());
+    }
+    for barcode_id in [5, 9, 15, 49, 95, 99] {
+        assert_eq!(
+            IntelligentMail::new(barcode_id, 234, "567094", "987654321").unwrap_err().to_string(),
+            format!(
+                "invalid Intelligent Mail barcode; barcode ID must have 2 digits with second digit 0-4; got {:02}",
+                barcode_id
+            )
+        );
+    }
+    assert_eq!(
+        IntelligentMail::from_str("05234567094987654321").unwrap_err().to_string(),
+        "invalid Intelligent Mail barcode; barcode ID must have 2 digits with second digit 0-4; got 05"
+    );
+    assert_eq!(
+        IntelligentMail::new(1, 1000, "567094", "987654321")
+            .unwrap_err()
+            .to_string(),
+        "invalid Intelligent Mail barcode; service type must have 3 digits; got 1000"
+    );
+}
+
+#[test]
+fn mailer_id_lengths() {
+    // 6 digit mailer ID is followed by 9 digit serial number
+    let imail = IntelligentMail::new(0, 270, "103502", "000000001").unwrap();
+    assert_eq!(imail.to_string(), "00270103502000000001");
+    assert_eq!(
+        IntelligentMail::new(0, 270, "103502", "000001")
+            .unwrap_err()
+            .to_string(),
+        "invalid Intelligent Mail barcode; serial number must have 9 digits; got \"000001\""
+    );
+
+    // 9 digit mailer ID starts with 9 and is followed by 6 digit serial number
+    let imail = IntelligentMail::new(0, 270, "999999999", "123456").unwrap();
+    assert_eq!(imail.to_string(), "00270999999999123456");
+    assert_eq!(
+        IntelligentMail::new(0, 270, "999999999", "123456789")
+            .unwrap_err()
+            .to_string(),
+        "invalid Intelligent Mail barcode; serial number must have 6 digits; got \"123456789\""
+    );
+    assert_eq!(
+        IntelligentMail::new(0, 270, "912345", "123456789").unwrap_err().to_string(),
+        "invalid Intelligent Mail barcode; mailer ID starting with 9 must have 9 digits; got \"912345\""
+    );
+    assert_eq!(
+        IntelligentMail::new(0, 270, "12345A", "123456789")
+            .unwrap_err()
+            .to_string(),
+        "invalid Intelligent Mail barcode; mailer ID must have 6 digits; got \"12345A\""
+    );
+}
+
+#[test]
+fn from_str_round_trip() {
+    for data in [
+        "01234567094987654321",
+        "0123456709498765432101234",
+        "01234567094987654321012345678",
+        "0123456709498765432101234567891",
+        "00270999999999123456123456789",
+    ] {
+        let imail = IntelligentMail::from_str(data).unwrap();
+        assert_eq!(imail.to_string(), data);
+    }
+
+    let imail = IntelligentMail::from_str("00270999999999123456-123456789").unwrap();
+    assert_eq!(
+        (
+            imail.barcode_id(),
+            imail.service_type(),
+            imail.mailer_id(),
+            imail.serial(),
+            imail.routing_code()
+        ),
+        (0, 270, "999999999", "123456", "123456789")
+    );
+    assert_eq!(imail.to_string(), "00270999999999123456123456789");
+
+    assert_eq!(
+        IntelligentMail::from_str("0123456709498765432").unwrap_err().to_string(),
+        "invalid Intelligent Mail barcode; tracking code must have 20 digits; got \"0123456709498765432\""
+    );
+    assert_eq!(
+        IntelligentMail::from_str("012345670949876543210123").unwrap_err().to_string(),
+        "invalid Intelligent Mail barcode; routing code must have 0, 5, 9 or 11 digits; got \"0123\""
+    );
+}
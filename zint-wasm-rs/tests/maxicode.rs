@@ -0,0 +1,114 @@
+use zint_wasm_rs::{
+    maxicode::CarrierMessage,
+    options::{
+        symbology::Symbology,
+        symbology_options::{MaxiCodeOptions, SymbologyOptions},
+    },
+};
+
+const SECONDARY: &str =
+    "1Z00004951\x1DUPSN\x1D06X610\x1D159\x1D1234567\x1D1/1\x1D\x1DY\x1D\x1D\x1DUT\x1E\x04";
+
+fn mode(message: &CarrierMessage) -> Option<SymbologyOptions> {
+    message.options().unwrap().symbology_options
+}
+
+#[test]
+fn scm_header() {
+    let message = CarrierMessage::new("152382802", 840, 1)
+        .unwrap()
+        .with_secondary(SECONDARY);
+    assert_eq!(message.data(), format!("[)>\x1E01\x1D96{}", SECONDARY));
+    assert_eq!(message.scm_version(), Some(96));
+
+    let message = message.with_scm_version(Some(1)).unwrap();
+    assert_eq!(message.data(), format!("[)>\x1E01\x1D01{}", SECONDARY));
+    let message = message.with_scm_version(None).unwrap();
+    assert_eq!(message.data(), SECONDARY);
+
+    assert_eq!(
+        CarrierMessage::new("152382802", 840, 1)
+            .unwrap()
+            .with_scm_version(Some(100))
+            .unwrap_err()
+            .to_string(),
+        "invalid MaxiCode message; SCM version must have 2 digits; got 100"
+    );
+}
+
+#[test]
+fn numeric_postcode() {
+    let message = CarrierMessage::new("152382802", 840, 1).unwrap();
+    assert!(message.is_numeric());
+    assert_eq!(message.mode(), 2);
+    assert_eq!(message.primary(), "152382802840001");
+
+    let options = message.options().unwrap();
+    assert_eq!(options.symbology, Symbology::MaxiCode);
+    assert_eq!(options.primary.as_deref(), Some("152382802840001"));
+    assert_eq!(
+        mode(&message),
+        Some(SymbologyOptions::MaxiCode(MaxiCodeOptions {
+            mode: Some(2)
+        }))
+    );
+
+    let message = CarrierMessage::new("12345", 840, 1).unwrap();
+    assert_eq!(message.mode(), 2);
+    assert_eq!(message.primary(), "12345840001");
+
+    assert_eq!(
+        CarrierMessage::new("1523828021", 840, 1)
+            .unwrap_err()
+            .to_string(),
+        "invalid MaxiCode message; numeric postcode must have up to 9 digits; got 10"
+    );
+}
+
+#[test]
+fn alphanumeric_postcode() {
+    let message = CarrierMessage::new("B1050", 56, 999).unwrap();
+    assert!(!message.is_numeric());
+    assert_eq!(message.mode(), 3);
+    assert_eq!(message.primary(), "B1050056999");
+    assert_eq!(
+        mode(&message),
+        Some(SymbologyOptions::MaxiCode(MaxiCodeOptions {
+            mode: Some(3)
+        }))
+    );
+    assert_eq!(CarrierMessage::new("SW1A 1", 826, 1).unwrap().mode(), 3);
+
+    assert_eq!(
+        CarrierMessage::new("SW1A 1AA", 826, 1)
+            .unwrap_err()
+            .to_string(),
+        "invalid MaxiCode message; alphanumeric postcode must have up to 6 characters; got 8"
+    );
+    assert_eq!(
+        CarrierMessage::new("b1050", 56, 1).unwrap_err().to_string(),
+        "invalid MaxiCode message; alphanumeric postcode can only contain A-Z, 0-9 and space; got 'b'"
+    );
+    assert_eq!(
+        CarrierMessage::new("", 56, 1).unwrap_err().to_string(),
+        "invalid MaxiCode message; postcode is empty"
+    );
+}
+
+#[test]
+fn country_and_service_class() {
+    assert_eq!(
+        CarrierMessage::new("152382802", 1000, 1)
+            .unwrap_err()
+            .to_string(),
+        "invalid MaxiCode message; country code must have 3 digits; got 1000"
+    );
+    assert_eq!(
+        CarrierMessage::new("152382802", 840, 1000)
+            .unwrap_err()
+            .to_string(),
+        "invalid MaxiCode message; service class must have 3 digits; got 1000"
+    );
+    let message = CarrierMessage::new("152382802", 999, 999).unwrap();
+    assert_eq!((message.country(), message.service_class()), (999, 999));
+}
@@ -0,0 +1,270 @@
+use zint_wasm_rs::options::{
+    color::Color,
+    input_mode::InputMode,
+    option3::{DataMatrixOption, Option3, QRMask, QRMatrixOption},
+    output_options::OutputOptions,
+    rotation::Rotation,
+    structured_append::StructuredAppend,
+    symbology::Symbology,
+    symbology_options::{
+        AztecEcc, AztecOptions, CheckDigit, CheckDigitOptions, Code11CheckDigits, Code11Options,
+        EanUpcOptions, MatrixOptions, MsiCheckDigit, MsiOptions, Pdf417Options, QrEcc, QrOptions,
+    },
+    Options,
+};
+
+fn qr_options() -> Options {
+    Options::builder(Symbology::QRCode)
+        .scale(2.0)
+        .fg_color(Color::Rgba {
+            r: 0x11,
+            g: 0x22,
+            b: 0x33,
+            a: u8::MAX,
+        })
+        .bg_color(Color::Cmyk {
+            c: 0,
+            m: 10,
+            y: 20,
+            k: 30,
+        })
+        .output_options(OutputOptions::BARCODE_BOX | OutputOptions::BOLD_TEXT)
+        .input_mode(InputMode::UNICODE | InputMode::ESCAPE)
+        .full_multibyte()
+        .symbology_options(QrOptions {
+            ecc: Some(QrEcc::H),
+            version: Some(5),
+        })
+        .rotation(Rotation::Deg90)
+        .structured_append(StructuredAppend::new(1, 2).unwrap().with_id("id").unwrap())
+        .build()
+        .unwrap()
+}
+
+fn all_options() -> Vec<Options> {
+    vec![
+        Options::default(),
+        qr_options(),
+        Options::builder(Symbology::QRCode)
+            .mask(QRMask::Mask3)
+            .fg_color(Color::Rgba {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0x80,
+            })
+            .input_mode(InputMode::DATA)
+            .build()
+            .unwrap(),
+        Options::builder(Symbology::DataMatrix)
+            .data_matrix(DataMatrixOption::Square)
+            .symbology_options(MatrixOptions {
+                ecc: None,
+                version: Some(10),
+            })
+            .input_mode(InputMode::GS1 | InputMode::GS1_PARENTHESES)
+            .build()
+            .unwrap(),
+        Options::builder(Symbology::Aztec)
+            .symbology_options(AztecOptions {
+                ecc: Some(AztecEcc::Percent23),
+                layers: Some(4),
+            })
+            .build()
+            .unwrap(),
+        Options::builder(Symbology::PDF417)
+            .symbology_options(Pdf417Options {
+                ecc: Some(3),
+                columns: Some(5),
+            })
+            .build()
+            .unwrap(),
+        Options::builder(Symbology::MSIPlessey)
+            .symbology_options(MsiOptions {
+                check_digit: MsiCheckDigit::Mod11Ncr,
+            })
+            .build()
+            .unwrap(),
+        Options::builder(Symbology::Code11)
+            .symbology_options(Code11Options {
+                check_digits: Code11CheckDigits::One,
+            })
+            .build()
+            .unwrap(),
+        Options::builder(Symbology::Code39)
+            .symbology_options(CheckDigitOptions {
+                check_digit: CheckDigit::Visible,
+            })
+            .build()
+            .unwrap(),
+        Options::builder(Symbology::EANX)
+            .symbology_options(EanUpcOptions {
+                addon_gap: Some(10),
+            })
+            .show_hrt(false)
+            .build()
+            .unwrap(),
+    ]
+}
+
+fn assert_same(parsed: &Options, options: &Options) {
+    assert_eq!(parsed.symbology, options.symbology);
+    assert_eq!(parsed.scale, options.scale);
+    assert_eq!(parsed.fg_color, options.fg_color);
+    assert_eq!(parsed.bg_color, options.bg_color);
+    assert_eq!(
+        parsed.output_options.map(|it| it.bits()),
+        options.output_options.map(|it| it.bits())
+    );
+    assert_eq!(
+        parsed.input_mode.map(|it| it.bits()),
+        options.input_mode.map(|it| it.bits())
+    );
+    assert_eq!(parsed.show_hrt, options.show_hrt);
+    assert_eq!(parsed.option_3, options.option_3);
+    assert_eq!(parsed.symbology_options, options.symbology_options);
+    assert_eq!(parsed.rotation, options.rotation);
+    assert_eq!(parsed.structured_append, options.structured_append);
+}
+
+#[test]
+fn json_round_trip() {
+    for options in all_options() {
+        let json = serde_json::to_string(&options).unwrap();
+        let parsed: Options = serde_json::from_str(&json).unwrap();
+        assert_same(&parsed, &options);
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+    }
+}
+
+#[test]
+fn cbor_round_trip() {
+    for options in all_options() {
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&options, &mut cbor).unwrap();
+        let parsed: Options = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_same(&parsed, &options);
+
+        let mut again = Vec::new();
+        ciborium::into_writer(&parsed, &mut again).unwrap();
+        assert_eq!(again, cbor);
+    }
+}
+
+#[test]
+#[cfg(not(feature = "typst"))]
+fn readable_json() {
+    let json = serde_json::to_value(qr_options()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "symbology": "QRCode",
+            "scale": 2.0,
+            "output_options": ["barcode-box", "bold-text"],
+            "fg_color": "112233",
+            "bg_color": "cmyk(0,10,20,30)",
+            "option_3": "full-multibyte",
+            "input_mode": ["unicode", "escape"],
+            "rotation": 90,
+            "structured_append": { "index": 1, "count": 2, "id": "id" },
+            "ecc": "H",
+            "version": 5,
+        })
+    );
+
+    let json = serde_json::to_value(Options::default()).unwrap();
+    assert_eq!(json, serde_json::json!({ "symbology": "Code128" }));
+}
+
+#[test]
+#[cfg(not(feature = "typst"))]
+fn scaling_out_of_range() {
+    use zint_wasm_rs::error::Error;
+
+    for (key, value) in [
+        ("x_dimension_mm", 0.0),
+        ("x_dimension_mm", 10.5),
+        ("dpmm", -1.0),
+        ("dpmm", 1001.0),
+    ] {
+        let json = serde_json::json!({ "symbology": "QRCode", key: value });
+        let error = serde_json::from_value::<Options>(json).unwrap_err();
+        assert!(error.to_string().contains(key), "{}", error);
+    }
+    let options: Options =
+        serde_json::from_value(serde_json::json!({ "x_dimension_mm": 10.0, "dpmm": 0.0 })).unwrap();
+    assert_eq!(
+        (options.x_dimension_mm, options.dpmm),
+        (Some(10.0), Some(0.0))
+    );
+
+    let error = Options::builder(Symbology::QRCode)
+        .x_dimension_mm(11.0)
+        .build()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::OutOfRange {
+            which: "x_dimension_mm",
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "x_dimension_mm must be between 0 and 10 for QRCode; got 11"
+    );
+    let error = Options::builder(Symbology::QRCode)
+        .dpmm(2000.0)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "dpmm must be between 0 and 1000 for QRCode; got 2000"
+    );
+}
+
+#[test]
+fn rotation() {
+    assert_eq!(Rotation::from_degrees(-90).unwrap(), Rotation::Deg270);
+    assert_eq!(Rotation::from_degrees(450).unwrap(), Rotation::Deg90);
+    assert!(Rotation::from_degrees(45).is_err());
+    assert!(Rotation::try_from(-90).is_err());
+
+    let rotation = |degrees: i32| {
+        serde_json::from_value::<Options>(serde_json::json!({ "rotation": degrees }))
+            .map(|it| it.rotation)
+    };
+    assert_eq!(rotation(270).unwrap(), Some(Rotation::Deg270));
+    assert!(rotation(45).is_err());
+    // typst angles are normalized
+    if cfg!(feature = "typst") {
+        assert_eq!(rotation(-90).unwrap(), Some(Rotation::Deg270));
+        assert_eq!(rotation(720).unwrap(), Some(Rotation::Deg0));
+    } else {
+        assert!(rotation(-90).is_err());
+    }
+}
+
+#[test]
+fn zero_option_3_is_unset() {
+    let key = if cfg!(feature = "typst") {
+        "option-3"
+    } else {
+        "option_3"
+    };
+    for symbology in [
+        Symbology::DataMatrix,
+        Symbology::QRCode,
+        Symbology::Ultra,
+        Symbology::Code128,
+    ] {
+        assert_eq!(Option3::new(symbology, 0).unwrap(), None);
+        let json = serde_json::json!({ "symbology": symbology.name(), key: 0 });
+        let options: Options = serde_json::from_value(json).unwrap();
+        assert_eq!(options.option_3, None, "{}", symbology);
+    }
+    assert!(Option3::new(Symbology::DataMatrix, 1).is_err());
+    assert!(Option3::from(QRMatrixOption::empty())
+        .check(Symbology::Code128)
+        .is_ok());
+}
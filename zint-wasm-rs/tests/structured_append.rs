@@ -0,0 +1,48 @@
+use zint_wasm_rs::options::{structured_append::StructuredAppend, symbology::Symbology, Options};
+
+#[test]
+fn qr_parts_share_parity() {
+    let options = Options::builder(Symbology::QRCode).build().unwrap();
+    let data = "Structured Append über QR Code".as_bytes();
+    let parity = data.iter().fold(0u8, |acc, it| acc ^ it).to_string();
+
+    let parts = StructuredAppend::split(&options, data, 3, None).unwrap();
+    assert_eq!(parts.len(), 3);
+    let joined: Vec<u8> = parts.iter().flat_map(|(_, part)| part.to_vec()).collect();
+    assert_eq!(joined, data);
+    for (i, (options, _)) in parts.iter().enumerate() {
+        let structured_append = options.structured_append.as_ref().unwrap();
+        assert_eq!(structured_append.index(), i as i32 + 1);
+        assert_eq!(structured_append.count(), 3);
+        assert_eq!(structured_append.id(), Some(parity.as_str()));
+    }
+
+    let parts = StructuredAppend::split(&options, data, 2, Some("7")).unwrap();
+    assert!(parts
+        .iter()
+        .all(|(options, _)| options.structured_append.as_ref().unwrap().id() == Some("7")));
+}
+
+#[test]
+fn ids_only_set_for_qr() {
+    let options = Options::builder(Symbology::DataMatrix).build().unwrap();
+    let parts = StructuredAppend::split(&options, b"0123456789", 2, None).unwrap();
+    assert!(parts.iter().all(|(options, _)| options
+        .structured_append
+        .as_ref()
+        .unwrap()
+        .id()
+        .is_none()));
+}
+
+#[test]
+fn hibc_variants() {
+    assert_eq!(StructuredAppend::max_count(Symbology::HIBCQR), Some(16));
+    assert_eq!(StructuredAppend::max_count(Symbology::HIBCDM), Some(16));
+    assert_eq!(StructuredAppend::max_count(Symbology::HIBCAztec), Some(26));
+    assert_eq!(StructuredAppend::max_count(Symbology::HIBCPDF), Some(99999));
+    assert_eq!(
+        StructuredAppend::max_count(Symbology::HIBCMicPDF),
+        Some(99999)
+    );
+}
@@ -0,0 +1,167 @@
+use zint_wasm_rs::{
+    error::Error,
+    options::{
+        capability::CapabilityFlags, color::Color, symbology::Symbology,
+        symbology_options::QrOptions, Options,
+    },
+    symbol::{Segment, Symbol},
+};
+
+/// Returns configured `option_1`, `option_2` and `height` of `symbol`.
+fn configuration(symbol: &Symbol) -> (i32, i32, f32) {
+    (symbol.option_1, symbol.option_2, symbol.height)
+}
+
+/// Returns whether all outputs of `symbol` are freed.
+fn outputs_freed(symbol: &Symbol) -> bool {
+    symbol.bitmap.is_null()
+        && symbol.alphamap.is_null()
+        && symbol.vector.is_null()
+        && symbol.memfile.is_null()
+}
+
+#[test]
+fn reuse_restores_configuration() {
+    let options = Options::builder(Symbology::QRCode).build().unwrap();
+    let mut symbol = Symbol::new(&options);
+    let configured = configuration(&symbol);
+    let long = "1234567890".repeat(20);
+
+    let first = symbol.encode_matrix("1").unwrap();
+    assert_eq!(configuration(&symbol), configured);
+    assert!(outputs_freed(&symbol));
+
+    // a larger version is selected for long data, but not kept for later
+    // encodings
+    let second = symbol.encode_matrix(&long).unwrap();
+    assert!(second.output.width() > first.output.width());
+    assert_eq!(configuration(&symbol), configured);
+    assert!(outputs_freed(&symbol));
+
+    let third = symbol.encode_matrix("1").unwrap();
+    assert_eq!(third.output, first.output);
+    assert_eq!(third.info, first.info);
+    assert_eq!(configuration(&symbol), configured);
+
+    for _ in 0..3 {
+        symbol.encode_svg(&long).unwrap();
+        assert!(outputs_freed(&symbol));
+        symbol.encode_vector(&long).unwrap();
+        assert!(outputs_freed(&symbol));
+        assert_eq!(configuration(&symbol), configured);
+    }
+}
+
+#[test]
+fn reuse_restores_height() {
+    let options = Options::builder(Symbology::Code128)
+        .height(20.0)
+        .build()
+        .unwrap();
+    let mut symbol = Symbol::new(&options);
+    assert_eq!(symbol.height, 20.0);
+    let first = symbol.encode_svg("Hello").unwrap();
+    let second = symbol.encode_svg("Hello").unwrap();
+    assert_eq!(first.output, second.output);
+    assert_eq!(symbol.height, 20.0);
+    assert!(outputs_freed(&symbol));
+
+    // zint picks a default height if it isn't set
+    let mut symbol = Symbol::new(&Options::builder(Symbology::Code128).build().unwrap());
+    let configured = configuration(&symbol);
+    let encoded = symbol.encode_matrix("Hello").unwrap();
+    assert!(encoded.info.height > 0.0);
+    assert_eq!(configuration(&symbol), configured);
+}
+
+#[test]
+fn failed_encoding_keeps_symbol_usable() {
+    let mut symbol = Symbol::new(&Options::builder(Symbology::EANX).build().unwrap());
+    let configured = configuration(&symbol);
+    assert!(matches!(
+        symbol.encode_svg("not a number"),
+        Err(Error::Zint { .. })
+    ));
+    assert_eq!(configuration(&symbol), configured);
+    assert!(outputs_freed(&symbol));
+    assert!(symbol.encode_svg("501234567890").is_ok());
+}
+
+#[test]
+fn vector_output() {
+    let options = Options::builder(Symbology::Code128)
+        .fg_color(Color::Rgba {
+            r: 0x11,
+            g: 0x22,
+            b: 0x33,
+            a: u8::MAX,
+        })
+        .build()
+        .unwrap();
+    let mut symbol = Symbol::new(&options);
+    let vector = symbol.encode_vector("Hello").unwrap().output;
+    assert!(vector.width > 0.0 && vector.height > 0.0);
+    assert!(!vector.rectangles.is_empty());
+    assert!(vector.strings.iter().any(|it| it.text == "Hello"));
+    assert_eq!(vector.foreground, options.fg_color.unwrap());
+    assert_eq!(vector.background, Color::TRANSPARENT);
+}
+
+#[test]
+fn matrix_output() {
+    let options = Options::builder(Symbology::QRCode)
+        .symbology_options(QrOptions {
+            ecc: None,
+            version: Some(1),
+        })
+        .build()
+        .unwrap();
+    let mut symbol = Symbol::new(&options);
+    let encoded = symbol.encode_matrix("Hello").unwrap();
+    let matrix = encoded.output;
+    assert_eq!((matrix.rows(), matrix.width()), (21, 21));
+    assert_eq!((encoded.info.rows, encoded.info.width), (21, 21));
+    // finder pattern in the top left corner, followed by separator
+    assert!((0..7).all(|col| matrix.is_set(0, col)));
+    assert!(!matrix.is_set(0, 7));
+    assert!(!matrix.is_set(1, 1));
+    assert!(matrix.is_set(3, 3));
+    assert_eq!(matrix.iter_rows().count(), 21);
+}
+
+#[test]
+fn segments() {
+    let mut symbol = Symbol::new(&Options::builder(Symbology::QRCode).build().unwrap());
+    let segments = [Segment::new("abc", 3), Segment::new("def", 26)];
+    assert!(symbol.encode_svg(&segments).is_ok());
+    assert!(outputs_freed(&symbol));
+
+    let mut symbol = Symbol::new(&Options::builder(Symbology::Code128).build().unwrap());
+    assert!(matches!(
+        symbol.encode_svg(&segments),
+        Err(Error::UnsupportedCapability(CapabilityFlags::Eci))
+    ));
+    // multiple segments require ECI support, a single one without ECI
+    // doesn't
+    assert!(symbol.encode_svg(&[Segment::new("abc", 0)]).is_ok());
+}
+
+#[test]
+fn x_dimension_scaling() {
+    let vector = |dpmm: f32| {
+        let options = Options::builder(Symbology::Code128)
+            .x_dimension_mm(0.5)
+            .dpmm(dpmm)
+            .build()
+            .unwrap();
+        let mut symbol = Symbol::new(&options);
+        let configured_scale = symbol.scale;
+        let vector = symbol.encode_vector("Hello").unwrap().output;
+        assert_eq!(symbol.scale, configured_scale);
+        vector
+    };
+    let small = vector(12.0);
+    let large = vector(24.0);
+    assert!((large.width / small.width - 2.0).abs() < 0.01);
+    assert!((large.height / small.height - 2.0).abs() < 0.01);
+}
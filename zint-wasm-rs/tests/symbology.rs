@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use zint_wasm_rs::{
+    error::Error,
+    options::{capability::CapabilityFlags, symbology::Symbology},
+};
+
+#[test]
+fn from_str() {
+    for name in [
+        "QRCode",
+        "qr-code",
+        "QR Code",
+        "qrcode",
+        "BARCODE_QRCODE",
+        "barcode_qrcode",
+        "58",
+    ] {
+        assert_eq!(
+            Symbology::from_str(name).unwrap(),
+            Symbology::QRCode,
+            "{}",
+            name
+        );
+    }
+    for symbology in Symbology::ALL {
+        assert_eq!(Symbology::from_str(symbology.name()).unwrap(), *symbology);
+        assert_eq!(Symbology::from_str(symbology.title()).unwrap(), *symbology);
+        assert_eq!(
+            Symbology::from_str(&symbology.id().to_string()).unwrap(),
+            *symbology
+        );
+    }
+
+    assert!(matches!(
+        Symbology::from_str("NotABarcode"),
+        Err(Error::UnknownOption {
+            which: "symbology",
+            ..
+        })
+    ));
+    assert!(matches!(
+        Symbology::from_str("9999"),
+        Err(Error::UnknownOption {
+            which: "symbology",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn aliases() {
+    for (alias, symbology) in [
+        ("EAN128", Symbology::GS1128),
+        ("RSS14", Symbology::DBarOmn),
+        ("RSSExpStack", Symbology::DBarExpStk),
+        ("PDF417Trunc", Symbology::PDF417Comp),
+        ("OneCode", Symbology::USPSIMail),
+        ("C25Matrix", Symbology::C25Standard),
+    ] {
+        assert_eq!(Symbology::from_str(alias).unwrap(), symbology, "{}", alias);
+        assert!(symbology.aliases().contains(&alias));
+    }
+    for symbology in Symbology::ALL {
+        for alias in symbology.aliases() {
+            assert_eq!(Symbology::from_str(alias).unwrap(), *symbology);
+        }
+    }
+}
+
+#[test]
+fn capabilities() {
+    let qr = Symbology::QRCode.capabilities();
+    for capability in [
+        CapabilityFlags::Eci,
+        CapabilityFlags::Mask,
+        CapabilityFlags::StructApp,
+        CapabilityFlags::FullMultibyte,
+        CapabilityFlags::Gs1,
+    ] {
+        assert!(qr.supports(capability), "{:?}", capability);
+    }
+    assert!(!qr.supports(CapabilityFlags::EanUpc));
+
+    assert!(Symbology::EANX.supports(CapabilityFlags::EanUpc));
+    assert!(!Symbology::Code128.supports(CapabilityFlags::Eci));
+    assert!(!Symbology::Code128.supports(CapabilityFlags::StructApp));
+    assert!(Symbology::PDF417.supports(CapabilityFlags::StructApp));
+    assert!(Symbology::HIBCQR.supports(CapabilityFlags::StructApp));
+}
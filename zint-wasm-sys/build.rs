@@ -14,6 +14,10 @@ fn watch_files(path: impl AsRef<Path>) {
     }
 }
 
+fn feature_enabled(name: &str) -> bool {
+    env::var_os(format!("CARGO_FEATURE_{}", name.to_uppercase())).is_some()
+}
+
 fn main() -> Result<()> {
     #[allow(non_snake_case)]
     let WASM = env::var("CARGO_CFG_TARGET_FAMILY")
@@ -53,12 +57,11 @@ fn main() -> Result<()> {
         }
     }
 
-    let files = [
+    let mut files = vec![
         "zint/backend/2of5.c",
         "zint/backend/auspost.c",
         "zint/backend/aztec.c",
         "zint/backend/bc412.c",
-        // "zint/backend/bmp.c",
         "zint/backend/codablock.c",
         "zint/backend/code128.c",
         "zint/backend/code16k.c",
@@ -71,10 +74,8 @@ fn main() -> Result<()> {
         "zint/backend/dmatrix.c",
         "zint/backend/dotcode.c",
         "zint/backend/eci.c",
-        // "zint/backend/emf.c",
         "zint/backend/filemem.c",
         "zint/backend/general_field.c",
-        // "zint/backend/gif.c",
         "zint/backend/gridmtx.c",
         "zint/backend/gs1.c",
         "zint/backend/hanxin.c",
@@ -85,19 +86,14 @@ fn main() -> Result<()> {
         "zint/backend/maxicode.c",
         "zint/backend/medical.c",
         "zint/backend/output.c",
-        // "zint/backend/pcx.c",
         "zint/backend/pdf417.c",
         "zint/backend/plessey.c",
-        // "zint/backend/png.c",
         "zint/backend/postal.c",
-        // "zint/backend/ps.c",
         "zint/backend/qr.c",
-        // "zint/backend/raster.c",
         "zint/backend/reedsol.c",
         "zint/backend/rss.c",
         "zint/backend/svg.c",
         "zint/backend/telepen.c",
-        // "zint/backend/tif.c",
         "zint/backend/ultra.c",
         "zint/backend/upcean.c",
         "zint/backend/vector.c",
@@ -107,6 +103,50 @@ fn main() -> Result<()> {
     // Build zint as a static library.
     let mut build = cc::Build::new();
 
+    // Output formats other than SVG are opt-in; formats that aren't enabled
+    // are replaced with stubs in patch.c.
+    if feature_enabled("raster") {
+        files.push("zint/backend/raster.c");
+        build.define("ZINT_WASM_RASTER", None);
+    }
+    for (feature, file) in [
+        ("eps", "zint/backend/ps.c"),
+        ("emf", "zint/backend/emf.c"),
+        ("bmp", "zint/backend/bmp.c"),
+        ("gif", "zint/backend/gif.c"),
+        ("pcx", "zint/backend/pcx.c"),
+        ("tif", "zint/backend/tif.c"),
+    ] {
+        if feature_enabled(feature) {
+            files.push(file);
+            build.define(&format!("ZINT_WASM_{}", feature.to_uppercase()), None);
+        }
+    }
+    if feature_enabled("png") {
+        // PNG output depends on libpng and zlib, which have to be provided by
+        // the environment.
+        files.push("zint/backend/png.c");
+        if let Ok(include_dir) = env::var("LIBPNG_INCLUDE_DIR") {
+            build.include(include_dir);
+        }
+        match env::var("LIBPNG_LIB_DIR") {
+            Ok(lib_dir) => println!("cargo:rustc-link-search=native={}", lib_dir),
+            // host libpng and zlib can't be linked into Wasm
+            Err(_) if WASM => panic!(
+                "`png` feature requires libpng and zlib built for {}; \
+                 set LIBPNG_LIB_DIR (and LIBPNG_INCLUDE_DIR) to point at them",
+                env::var("TARGET").unwrap_or_default()
+            ),
+            Err(_) => {}
+        }
+        println!("cargo:rustc-link-lib=png");
+        println!("cargo:rustc-link-lib=z");
+    } else {
+        build.define("ZINT_NO_PNG", None);
+    }
+    println!("cargo:rerun-if-env-changed=LIBPNG_INCLUDE_DIR");
+    println!("cargo:rerun-if-env-changed=LIBPNG_LIB_DIR");
+
     build
         .files(files)
         .define("_GNU_SOURCE", None)
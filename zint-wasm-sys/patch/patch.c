@@ -1,11 +1,47 @@
+#include <string.h>
 #include "../zint/backend/zint.h"
-// we only need svg plot, so patch the rest to empty functions
+// output formats that aren't enabled through cargo features are patched to
+// empty functions
+
+#ifndef ZINT_WASM_RASTER
 int plot_raster(struct zint_symbol *symbol, int rotate_angle, int file_type) {
     return 0;
 }
+#else
+static int disabled_pixel_plot(struct zint_symbol *symbol, const char *format) {
+    strcpy(symbol->errtxt, format);
+    strcat(symbol->errtxt, " output not enabled in this build");
+    return ZINT_ERROR_INVALID_OPTION;
+}
+#ifndef ZINT_WASM_BMP
+int bmp_pixel_plot(struct zint_symbol *symbol, const unsigned char *pixelbuf) {
+    return disabled_pixel_plot(symbol, "BMP");
+}
+#endif
+#ifndef ZINT_WASM_GIF
+int gif_pixel_plot(struct zint_symbol *symbol, const unsigned char *pixelbuf) {
+    return disabled_pixel_plot(symbol, "GIF");
+}
+#endif
+#ifndef ZINT_WASM_PCX
+int pcx_pixel_plot(struct zint_symbol *symbol, const unsigned char *pixelbuf) {
+    return disabled_pixel_plot(symbol, "PCX");
+}
+#endif
+#ifndef ZINT_WASM_TIF
+int tif_pixel_plot(struct zint_symbol *symbol, const unsigned char *pixelbuf) {
+    return disabled_pixel_plot(symbol, "TIF");
+}
+#endif
+#endif
+
+#ifndef ZINT_WASM_EPS
 int ps_plot(struct zint_symbol *symbol, int rotate_angle) {
     return 0;
 }
+#endif
+#ifndef ZINT_WASM_EMF
 int emf_plot(struct zint_symbol *symbol, int rotate_angle) {
     return 0;
 }
+#endif